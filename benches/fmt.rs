@@ -0,0 +1,25 @@
+use bitarr::BitSet;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::fmt::Write;
+
+fn binary(c: &mut Criterion) {
+	let mut group = c.benchmark_group("binary");
+
+	let mut bs = BitSet::from([0u64; 64]);
+	for i in (0..bs.len()).step_by(3) {
+		bs.set(i);
+	}
+
+	let mut out = String::with_capacity(8192);
+	group.bench_function("[u64; 64]", |b| {
+		b.iter(|| {
+			out.clear();
+			write!(out, "{:b}", black_box(&bs)).unwrap();
+		})
+	});
+
+	group.finish();
+}
+
+criterion_group!(benches, binary);
+criterion_main!(benches);
@@ -0,0 +1,86 @@
+use bitarr::store::{BitStore, BitStoreConst, BitStoreMut};
+use bitarr::BitSet;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn is_empty(c: &mut Criterion) {
+	let mut group = c.benchmark_group("is_empty");
+
+	let empty = <[u64; 1024] as BitStoreConst>::EMPTY;
+	group.bench_function("[u64; 1024] empty", |b| {
+		b.iter(|| BitStore::is_empty(black_box(&empty)))
+	});
+
+	let mut first = <[u64; 1024] as BitStoreConst>::EMPTY;
+	unsafe { first.set(0) };
+	group.bench_function("[u64; 1024] first bit set", |b| {
+		b.iter(|| BitStore::is_empty(black_box(&first)))
+	});
+
+	group.finish();
+}
+
+fn is_full(c: &mut Criterion) {
+	let mut group = c.benchmark_group("is_full");
+
+	let full = <[u64; 1024] as BitStoreConst>::FULL;
+	group.bench_function("[u64; 1024] full", |b| {
+		b.iter(|| BitStore::is_full(black_box(&full)))
+	});
+
+	let mut first = <[u64; 1024] as BitStoreConst>::FULL;
+	unsafe { first.unset(0) };
+	group.bench_function("[u64; 1024] first bit unset", |b| {
+		b.iter(|| BitStore::is_full(black_box(&first)))
+	});
+
+	group.finish();
+}
+
+fn count_ones(c: &mut Criterion) {
+	let mut group = c.benchmark_group("count_ones");
+
+	let mut sparse = <[u64; 300] as BitStoreConst>::EMPTY;
+	for i in (0..<[u64; 300] as BitStore>::BITS).step_by(97) {
+		unsafe { sparse.set(i) };
+	}
+
+	group.bench_function("[u64; 300] sparse", |b| {
+		b.iter(|| BitStore::count_ones(black_box(&sparse)))
+	});
+
+	let full = <[u64; 1024] as BitStoreConst>::FULL;
+	group.bench_function("[u64; 1024] full", |b| {
+		b.iter(|| BitStore::count_ones(black_box(&full)))
+	});
+
+	group.finish();
+}
+
+fn ones(c: &mut Criterion) {
+	let mut group = c.benchmark_group("ones");
+
+	// Roughly 1% of the bits are set.
+	let mut sparse = BitSet::from(<[u64; 16] as BitStoreConst>::EMPTY);
+	for i in (0..sparse.len()).step_by(97) {
+		sparse.set(i);
+	}
+
+	group.bench_function("[u64; 16] sparse word-wise", |b| {
+		b.iter(|| black_box(&sparse).ones().sum::<u32>())
+	});
+
+	group.bench_function("[u64; 16] sparse bit-wise", |b| {
+		b.iter(|| {
+			black_box(&sparse)
+				.iter()
+				.enumerate()
+				.filter_map(|(i, b)| b.then_some(i as u32))
+				.sum::<u32>()
+		})
+	});
+
+	group.finish();
+}
+
+criterion_group!(benches, is_empty, is_full, count_ones, ones);
+criterion_main!(benches);
@@ -1,35 +1,105 @@
 use crate::store::BitStore;
-use core::fmt::{self, Write};
+use core::fmt;
 
 pub(crate) struct BinaryDisplay<'a, S: BitStore>(pub(crate) &'a S);
 
 impl<'a, S: BitStore> fmt::Binary for BinaryDisplay<'a, S> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		let bits = self.0;
+		// Output is batched through a small buffer, as writing to the
+		// formatter one character at a time is slow for wide stores.
+		let mut buf = [0u8; 64];
+		let mut len = 0;
 		f.write_str("0b")?;
 		for bit in (0..S::BITS).rev() {
+			// Room for an underscore and a digit.
+			if len + 2 > buf.len() {
+				flush(f, &buf[..len])?;
+				len = 0;
+			}
+
 			if (bit + 1) % 4 == 0 {
-				f.write_char('_')?;
+				buf[len] = b'_';
+				len += 1;
 			}
 
 			// SAFETY: `bit` is in range.
-			let value = unsafe { bits.get(bit) };
-			if value {
-				f.write_char('1')?;
-			} else {
-				f.write_char('0')?;
-			}
+			buf[len] = b'0' + unsafe { bits.bit(bit) } as u8;
+			len += 1;
+		}
+
+		flush(f, &buf[..len])
+	}
+}
+
+pub(crate) struct HexDisplay<'a, S: BitStore>(pub(crate) &'a S);
+
+impl<'a, S: BitStore> fmt::LowerHex for HexDisplay<'a, S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write_digits(f, self.0, 4, b"0123456789abcdef", "0x")
+	}
+}
+
+impl<'a, S: BitStore> fmt::UpperHex for HexDisplay<'a, S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write_digits(f, self.0, 4, b"0123456789ABCDEF", "0x")
+	}
+}
+
+pub(crate) struct OctalDisplay<'a, S: BitStore>(pub(crate) &'a S);
+
+impl<'a, S: BitStore> fmt::Octal for OctalDisplay<'a, S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write_digits(f, self.0, 3, b"01234567", "0o")
+	}
+}
+
+/// Writes the bits as digits of `width` bits each, most significant first,
+/// with `prefix` in front if the alternate flag is set. When `S::BITS` is not
+/// a multiple of `width`, the first digit only holds the leftover high bits.
+fn write_digits<S: BitStore>(
+	f: &mut fmt::Formatter<'_>,
+	bits: &S,
+	width: u32,
+	digits: &[u8],
+	prefix: &str,
+) -> fmt::Result {
+	if f.alternate() {
+		f.write_str(prefix)?;
+	}
+
+	let mut buf = [0u8; 64];
+	let mut len = 0;
+	for digit in (0..S::BITS.div_ceil(width)).rev() {
+		if len == buf.len() {
+			flush(f, &buf)?;
+			len = 0;
 		}
 
-		Ok(())
+		let start = digit * width;
+		let value = (start..S::BITS.min(start + width)).fold(0, |value, i| {
+			// SAFETY: `i` is in range.
+			value | unsafe { bits.bit(i) } << (i - start)
+		});
+		buf[len] = digits[value as usize];
+		len += 1;
 	}
+
+	flush(f, &buf[..len])
+}
+
+#[inline]
+fn flush(f: &mut fmt::Formatter<'_>, buf: &[u8]) -> fmt::Result {
+	// SAFETY: the buffer only ever contains ASCII.
+	f.write_str(unsafe { core::str::from_utf8_unchecked(buf) })
 }
 
 #[cfg(all(test, feature = "alloc"))]
 mod tests {
 	use super::*;
 	use crate::store::{BitStoreConst, BitStoreMut};
-	use alloc::format;
+	use alloc::{format, string::String};
+	use core::fmt::Write;
 
 	struct DisplayWrapper<S: BitStore>(S);
 
@@ -59,4 +129,66 @@ mod tests {
 			"0b_0001_0000_0000_0100"
 		);
 	}
+
+	fn unbatched<S: BitStore>(bits: &S) -> String {
+		let mut out = String::from("0b");
+		for bit in (0..S::BITS).rev() {
+			if (bit + 1) % 4 == 0 {
+				out.write_char('_').unwrap();
+			}
+
+			out
+				.write_char(if unsafe { bits.get(bit) } { '1' } else { '0' })
+				.unwrap();
+		}
+
+		out
+	}
+
+	#[test]
+	fn batched_output_is_unchanged() {
+		let mut bits = [0u64; 64];
+		for i in (0..<[u64; 64] as BitStore>::BITS).filter(|i| i % 3 == 0 || i % 7 == 0) {
+			unsafe { BitStoreMut::set(&mut bits, i) };
+		}
+
+		assert_eq!(format!("{}", DisplayWrapper(bits)), unbatched(&bits));
+		assert_eq!(
+			format!("{}", DisplayWrapper([0x5au8; 3])),
+			unbatched(&[0x5au8; 3])
+		);
+		assert_eq!(format!("{}", DisplayWrapper([true, false, true])), "0b101");
+		assert_eq!(
+			format!("{}", DisplayWrapper(u128::MAX)),
+			unbatched(&u128::MAX)
+		);
+	}
+
+	#[test]
+	fn hex() {
+		assert_eq!(format!("{:x}", HexDisplay(&0xbeefu16)), "beef");
+		assert_eq!(format!("{:#X}", HexDisplay(&0xbeefu16)), "0xBEEF");
+		assert_eq!(format!("{:x}", HexDisplay(&0x000fu16)), "000f");
+		assert_eq!(format!("{:#x}", HexDisplay(&[0xaau8, 0xbb])), "0xbbaa");
+		assert_eq!(format!("{:X}", HexDisplay(&[0xaau8, 0xbb])), "BBAA");
+		assert_eq!(
+			format!("{:x}", HexDisplay(&[true, false, true, true, true])),
+			"1d"
+		);
+
+		let bits = [0x0123_4567_89ab_cdefu64; 5];
+		assert_eq!(
+			format!("{:x}", HexDisplay(&bits)),
+			"0123456789abcdef".repeat(5)
+		);
+	}
+
+	#[test]
+	fn octal() {
+		assert_eq!(format!("{:o}", OctalDisplay(&0o377u8)), "377");
+		assert_eq!(format!("{:#o}", OctalDisplay(&0o017u8)), "0o017");
+		assert_eq!(format!("{:o}", OctalDisplay(&0o123456u16)), "123456");
+		assert_eq!(format!("{:#o}", OctalDisplay(&0o000007u16)), "0o000007");
+		assert_eq!(format!("{:o}", OctalDisplay(&[0xffu8, 0x01])), "000777");
+	}
 }
@@ -0,0 +1,204 @@
+//! Bit sets that can be modified through a shared reference.
+
+use crate::store::BitStoreShared;
+use crate::BitSet;
+use core::cell::Cell;
+use core::fmt;
+
+/// A compact data structure for storing bits, that can be modified through a
+/// shared reference. This is the single-threaded counterpart of sharing a
+/// `BitSet` behind a lock, and is backed by [Cell]s.
+///
+/// # Examples
+///
+/// ```
+/// # use bitarr::cell::CellBitSet;
+/// # use core::cell::Cell;
+/// let bs = CellBitSet::from(Cell::new(0u8));
+/// let shared = &bs;
+///
+/// shared.set(3);
+/// shared.set(7);
+///
+/// assert_eq!(bs.get(3), Some(true));
+/// assert_eq!(bs.get(7), Some(true));
+/// assert_eq!(bs.get(2), Some(false));
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Default)]
+pub struct CellBitSet<S: BitStoreShared = Cell<usize>> {
+	bits: S,
+}
+
+impl<S: BitStoreShared> fmt::Debug for CellBitSet<S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Binary::fmt(&self.as_bitset(), f)
+	}
+}
+
+impl<S: BitStoreShared> From<S> for CellBitSet<S> {
+	#[inline]
+	fn from(bits: S) -> Self {
+		Self { bits }
+	}
+}
+
+impl<S: BitStoreShared> CellBitSet<S> {
+	/// Gets the value of the bit at the specified index.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::cell::CellBitSet;
+	/// # use core::cell::Cell;
+	/// let bs = CellBitSet::from(Cell::new(0b1000u8));
+	/// assert_eq!(bs.get(3), Some(true));
+	/// assert_eq!(bs.get(8), None);
+	/// ```
+	#[inline]
+	pub fn get(&self, index: u32) -> Option<bool> {
+		if index >= S::BITS {
+			None
+		} else {
+			// SAFETY: The index is in bounds
+			Some(unsafe { self.bits.get(index) })
+		}
+	}
+
+	/// Sets the bit at the specified index, and returns
+	/// original value.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::cell::CellBitSet;
+	/// # use core::cell::Cell;
+	/// let bs = CellBitSet::from(Cell::new(0u8));
+	/// assert_eq!(bs.set(3), Some(false));
+	/// assert_eq!(bs.get(3), Some(true));
+	/// ```
+	#[inline]
+	pub fn set(&self, index: u32) -> Option<bool> {
+		if index >= S::BITS {
+			None
+		} else {
+			// SAFETY: The index is in bounds
+			unsafe {
+				let old = self.bits.get(index);
+				self.bits.set_shared(index);
+				Some(old)
+			}
+		}
+	}
+
+	/// Unsets the bit at the specified index, and returns
+	/// original value.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::cell::CellBitSet;
+	/// # use core::cell::Cell;
+	/// let bs = CellBitSet::from(Cell::new(0b1000u8));
+	/// assert_eq!(bs.unset(3), Some(true));
+	/// assert_eq!(bs.get(3), Some(false));
+	/// ```
+	#[inline]
+	pub fn unset(&self, index: u32) -> Option<bool> {
+		if index >= S::BITS {
+			None
+		} else {
+			// SAFETY: The index is in bounds
+			unsafe {
+				let old = self.bits.get(index);
+				self.bits.unset_shared(index);
+				Some(old)
+			}
+		}
+	}
+
+	/// Changes the bit at the specified index to `value`, and returns
+	/// original value.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::cell::CellBitSet;
+	/// # use core::cell::Cell;
+	/// let bs = CellBitSet::from(Cell::new(0u8));
+	/// assert_eq!(bs.change(3, true), Some(false));
+	/// assert_eq!(bs.change(3, false), Some(true));
+	/// ```
+	#[inline]
+	pub fn change(&self, index: u32, value: bool) -> Option<bool> {
+		if value {
+			self.set(index)
+		} else {
+			self.unset(index)
+		}
+	}
+
+	/// Returns the number of bits in the `CellBitSet`.
+	#[inline]
+	pub const fn len(&self) -> u32 {
+		S::BITS
+	}
+
+	/// Returns `true` if the `CellBitSet` is empty, i.e., all bits are unset.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.bits.is_empty()
+	}
+
+	/// Returns a read-only `BitSet` view of the current bits.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::cell::CellBitSet;
+	/// # use core::cell::Cell;
+	/// let bs = CellBitSet::from(Cell::new(0u8));
+	/// bs.set(3);
+	/// assert!(bs.as_bitset().ones().eq([3]));
+	/// ```
+	#[inline]
+	pub fn as_bitset(&self) -> BitSet<&S> {
+		BitSet::from(&self.bits)
+	}
+
+	/// Returns the backing store.
+	#[inline]
+	pub fn into_inner(self) -> S {
+		self.bits
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn mutate_through_shared_reference() {
+		let bs = CellBitSet::<[Cell<u8>; 2]>::default();
+
+		let set_all = |bs: &CellBitSet<[Cell<u8>; 2]>, indices: &[u32]| {
+			for &i in indices {
+				assert_eq!(bs.set(i), Some(false));
+			}
+		};
+
+		set_all(&bs, &[1, 8, 15]);
+		assert!(bs.as_bitset().is_exactly(&[1, 8, 15]));
+
+		let shared = &bs;
+		assert_eq!(shared.unset(8), Some(true));
+		assert_eq!(shared.set(16), None);
+		assert_eq!(shared.unset(16), None);
+		assert!(!shared.is_empty());
+
+		assert_eq!(
+			bs.into_inner().map(Cell::into_inner),
+			[0b0000_0010, 0b1000_0000]
+		);
+	}
+}
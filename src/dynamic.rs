@@ -0,0 +1,269 @@
+//! Bit sets whose length is only known at runtime.
+
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
+
+/// A compact data structure for storing a sequence of bits whose length is
+/// only known at runtime, backed by a [Vec] of words.
+///
+/// # Examples
+///
+/// ```
+/// # use bitarr::dynamic::DynBitSet;
+/// let bs = DynBitSet::from_bits_dynamic([true, false, true]);
+///
+/// assert_eq!(bs.bits(), 3);
+/// assert_eq!(bs.get(0), Some(true));
+/// assert_eq!(bs.get(1), Some(false));
+/// assert_eq!(bs.get(3), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DynBitSet {
+	// Bits past `len` in the last word are always 0.
+	words: Vec<usize>,
+	len: u32,
+}
+
+impl DynBitSet {
+	/// Creates an empty `DynBitSet`, holding no bits.
+	#[inline]
+	pub const fn new() -> Self {
+		Self {
+			words: Vec::new(),
+			len: 0,
+		}
+	}
+
+	/// Creates an empty `DynBitSet` with room for at least `bits` bits before
+	/// it needs to reallocate. The capacity is rounded up to whole words.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::dynamic::DynBitSet;
+	/// let bs = DynBitSet::with_capacity(100);
+	/// assert_eq!(bs.bits(), 0);
+	/// assert!(bs.capacity() >= 100);
+	/// ```
+	#[inline]
+	pub fn with_capacity(bits: u32) -> Self {
+		Self {
+			words: Vec::with_capacity(Self::words_for(bits)),
+			len: 0,
+		}
+	}
+
+	/// Creates a `DynBitSet` from a stream of bits, growing the backing
+	/// storage as needed. The first bit yielded ends up at index 0, and the
+	/// length is the number of bits yielded.
+	///
+	/// This is the dynamic counterpart of collecting into a `BitSet`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::dynamic::DynBitSet;
+	/// let bs = DynBitSet::from_bits_dynamic((0..100).map(|i| i % 3 == 0));
+	/// assert_eq!(bs.bits(), 100);
+	/// assert_eq!(bs.get(99), Some(true));
+	/// ```
+	pub fn from_bits_dynamic<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+		let mut bs = Self::new();
+		for bit in iter {
+			bs.push(bit);
+		}
+
+		bs
+	}
+
+	/// Returns the number of bits in the `DynBitSet`.
+	#[inline]
+	pub const fn bits(&self) -> u32 {
+		self.len
+	}
+
+	/// Returns the number of bits the `DynBitSet` can hold without
+	/// reallocating. This is always a whole number of words.
+	#[inline]
+	pub fn capacity(&self) -> u32 {
+		(self.words.capacity() as u32).saturating_mul(usize::BITS)
+	}
+
+	/// Reserves room for at least `additional_bits` more bits, so that
+	/// appending them doesn't reallocate. The capacity is rounded up to whole
+	/// words, and like [Vec::reserve] more may be reserved to avoid frequent
+	/// reallocations.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::dynamic::DynBitSet;
+	/// let mut bs = DynBitSet::from_bits_dynamic([true]);
+	/// bs.reserve(200);
+	/// assert!(bs.capacity() >= 201);
+	/// ```
+	#[inline]
+	pub fn reserve(&mut self, additional_bits: u32) {
+		let words = Self::words_for(self.len.saturating_add(additional_bits));
+		self.words.reserve(words.saturating_sub(self.words.len()));
+	}
+
+	/// Gets the value of the bit at the specified index.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::dynamic::DynBitSet;
+	/// let bs = DynBitSet::from_bits_dynamic([false, true]);
+	/// assert_eq!(bs.get(1), Some(true));
+	/// assert_eq!(bs.get(2), None);
+	/// ```
+	#[inline]
+	pub fn get(&self, index: u32) -> Option<bool> {
+		if index >= self.len {
+			return None;
+		}
+
+		let (word, bit) = Self::split(index);
+		Some(self.words[word] & (1 << bit) != 0)
+	}
+
+	/// Appends a bit at the end of the `DynBitSet`.
+	///
+	/// # Panics
+	/// Panics if the `DynBitSet` already holds `u32::MAX` bits.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::dynamic::DynBitSet;
+	/// let mut bs = DynBitSet::new();
+	/// bs.push(true);
+	/// assert_eq!(bs.bits(), 1);
+	/// assert_eq!(bs.get(0), Some(true));
+	/// ```
+	pub fn push(&mut self, bit: bool) {
+		let len = self.len.checked_add(1).expect("DynBitSet length overflow");
+		let (word, offset) = Self::split(self.len);
+		if word == self.words.len() {
+			self.words.push(0);
+		}
+
+		self.words[word] |= (bit as usize) << offset;
+		self.len = len;
+	}
+
+	#[inline]
+	fn words_for(bits: u32) -> usize {
+		bits.div_ceil(usize::BITS) as usize
+	}
+
+	#[inline]
+	fn split(index: u32) -> (usize, u32) {
+		((index / usize::BITS) as usize, index % usize::BITS)
+	}
+}
+
+/// Hashes the length followed by the words up to the highest set bit, so
+/// the hash only depends on the logical bits and not on how much capacity
+/// has been allocated. Fixed-size stores like arrays and boxes have no such
+/// slack, so `BitSet` simply hashes its whole store.
+impl Hash for DynBitSet {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.len.hash(state);
+		let significant = self
+			.words
+			.iter()
+			.rposition(|&word| word != 0)
+			.map_or(0, |i| i + 1);
+		for word in &self.words[..significant] {
+			word.hash(state);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn from_bits_dynamic_grows() {
+		let bs = DynBitSet::from_bits_dynamic((0..100).map(|i| i % 7 == 0));
+		assert_eq!(bs.bits(), 100);
+		for i in 0..100 {
+			assert_eq!(bs.get(i), Some(i % 7 == 0), "bit {}", i);
+		}
+		assert_eq!(bs.get(100), None);
+
+		let empty = DynBitSet::from_bits_dynamic(core::iter::empty());
+		assert_eq!(empty.bits(), 0);
+		assert_eq!(empty, DynBitSet::new());
+	}
+
+	#[test]
+	fn with_capacity_does_not_reallocate() {
+		let mut bs = DynBitSet::with_capacity(1000);
+		let capacity = bs.capacity();
+		assert!(capacity >= 1000);
+		assert_eq!(capacity % usize::BITS, 0);
+
+		for i in 0..1000 {
+			bs.push(i == 999);
+		}
+		assert_eq!(bs.get(999), Some(true));
+		assert_eq!(bs.capacity(), capacity);
+	}
+
+	#[test]
+	fn reserve_rounds_to_words() {
+		let mut bs = DynBitSet::new();
+		assert_eq!(bs.capacity(), 0);
+
+		bs.reserve(1);
+		assert!(bs.capacity() >= usize::BITS);
+
+		let capacity = bs.capacity();
+		for _ in 0..usize::BITS {
+			bs.push(true);
+		}
+		assert_eq!(bs.capacity(), capacity);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn hash_ignores_capacity() {
+		use std::collections::hash_map::DefaultHasher;
+
+		fn hash_of(bs: &DynBitSet) -> u64 {
+			let mut hasher = DefaultHasher::new();
+			bs.hash(&mut hasher);
+			hasher.finish()
+		}
+
+		let bits = (0..150).map(|i| i % 11 == 0 && i < 70);
+		let small = DynBitSet::from_bits_dynamic(bits.clone());
+		let mut large = DynBitSet::with_capacity(4096);
+		for bit in bits {
+			large.push(bit);
+		}
+
+		assert_ne!(small.capacity(), large.capacity());
+		assert_eq!(small, large);
+		assert_eq!(hash_of(&small), hash_of(&large));
+
+		let shorter = DynBitSet::from_bits_dynamic((0..149).map(|i| i % 11 == 0 && i < 70));
+		assert_ne!(hash_of(&small), hash_of(&shorter));
+	}
+
+	#[test]
+	#[should_panic(expected = "DynBitSet length overflow")]
+	fn push_past_max_len() {
+		// The length is checked before the words are touched, so they don't
+		// need to be allocated for this.
+		let mut bs = DynBitSet {
+			words: Vec::new(),
+			len: u32::MAX,
+		};
+		bs.push(false);
+	}
+}
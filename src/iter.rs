@@ -1,5 +1,7 @@
-use crate::store::BitStore;
-use core::ops;
+use crate::store::{self, BitStore, BitWord};
+use crate::BitSet;
+use alloc::vec::Vec;
+use core::{fmt, ops};
 
 #[derive(Debug, Clone)]
 pub struct Bits<S: BitStore> {
@@ -72,3 +74,493 @@ impl<S: BitStore> ExactSizeIterator for Bits<S> {
 }
 
 impl<S: BitStore> core::iter::FusedIterator for Bits<S> {}
+
+/// Returns an iterator over the indices that are set in any of `sets`, in
+/// ascending order and without duplicates.
+///
+/// The sets are merged as they are iterated, without creating their union,
+/// so taking the first few indices of a large union stays cheap. Each set
+/// keeps its own position and is only scanned forward once.
+///
+/// # Examples
+///
+/// ```
+/// # use bitarr::{iter, BitSet};
+/// let sets = [
+///     BitSet::from(0b0000_0101u8),
+///     BitSet::from(0b0001_0100u8),
+///     BitSet::from(0b1000_0000u8),
+/// ];
+/// assert!(iter::union_ones(&sets).eq([0, 2, 4, 7]));
+/// ```
+pub fn union_ones<S: BitStore>(sets: &[BitSet<S>]) -> impl Iterator<Item = u32> + '_ {
+	let mut heads = sets
+		.iter()
+		.map(|set| set.bits.next_one(0))
+		.collect::<Vec<_>>();
+	core::iter::from_fn(move || {
+		let next = heads.iter().flatten().copied().min()?;
+		for (head, set) in heads.iter_mut().zip(sets) {
+			if *head == Some(next) {
+				*head = set.bits.next_one(next + 1);
+			}
+		}
+
+		Some(next)
+	})
+}
+
+/// An iterator over the indices of the set bits of a store, in ascending
+/// order.
+///
+/// The store is read one word at a time, so empty words are skipped without
+/// looking at their bits. Note that [ExactSizeIterator::len] counts the
+/// set bits of all the words that haven't been read yet.
+#[derive(Debug, Clone)]
+pub struct Ones<S: BitStore> {
+	bits: S,
+	// Words that haven't been loaded into `front` or `back` yet.
+	words: ops::Range<u32>,
+	front: WordOnes<S::Word>,
+	back: WordOnes<S::Word>,
+}
+
+impl<S: BitStore> From<S> for Ones<S> {
+	#[inline]
+	fn from(bits: S) -> Self {
+		Self::new(bits)
+	}
+}
+
+impl<S: BitStore> Ones<S> {
+	#[inline]
+	pub const fn new(bits: S) -> Self {
+		Self {
+			bits,
+			words: 0..S::WORDS,
+			front: WordOnes::new(<S::Word as store::BitStoreConst>::EMPTY, 0),
+			back: WordOnes::new(<S::Word as store::BitStoreConst>::EMPTY, 0),
+		}
+	}
+
+	#[inline]
+	fn load(&self, index: u32) -> WordOnes<S::Word> {
+		// SAFETY: `words` only contains indices in range 0..S::WORDS.
+		let word = unsafe { self.bits.word(index) };
+		WordOnes::new(word, index * <S::Word as BitStore>::BITS)
+	}
+}
+
+impl<S: BitStore> Iterator for Ones<S> {
+	type Item = u32;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some(index) = self.front.next() {
+				return Some(index);
+			}
+
+			match self.words.next() {
+				Some(i) => self.front = self.load(i),
+				None => return self.back.next(),
+			}
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let loaded = self.front.word.count_ones() + self.back.word.count_ones();
+		let unloaded = self
+			.words
+			.clone()
+			.map(|i| self.load(i).word.count_ones())
+			.sum::<u32>();
+		let len = (loaded + unloaded) as usize;
+		(len, Some(len))
+	}
+}
+
+impl<S: BitStore> DoubleEndedIterator for Ones<S> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some(index) = self.back.next_back() {
+				return Some(index);
+			}
+
+			match self.words.next_back() {
+				Some(i) => self.back = self.load(i),
+				None => return self.front.next_back(),
+			}
+		}
+	}
+}
+
+impl<S: BitStore> ExactSizeIterator for Ones<S> {}
+
+impl<S: BitStore> core::iter::FusedIterator for Ones<S> {}
+
+/// An owning iterator over the indices of the set bits of a store, in
+/// ascending order.
+///
+/// Like [Ones], it reads the store one word at a time.
+#[derive(Clone)]
+pub struct IntoOnes<S: BitStore> {
+	ones: Ones<S>,
+}
+
+impl<S: BitStore> fmt::Debug for IntoOnes<S>
+where
+	Ones<S>: fmt::Debug,
+{
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("IntoOnes")
+			.field("ones", &self.ones)
+			.finish()
+	}
+}
+
+impl<S: BitStore> From<S> for IntoOnes<S> {
+	#[inline]
+	fn from(bits: S) -> Self {
+		Self::new(bits)
+	}
+}
+
+impl<S: BitStore> IntoOnes<S> {
+	#[inline]
+	pub const fn new(bits: S) -> Self {
+		Self {
+			ones: Ones::new(bits),
+		}
+	}
+}
+
+impl<S: BitStore> Iterator for IntoOnes<S> {
+	type Item = u32;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.ones.next()
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.ones.size_hint()
+	}
+}
+
+impl<S: BitStore> DoubleEndedIterator for IntoOnes<S> {
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.ones.next_back()
+	}
+}
+
+impl<S: BitStore> ExactSizeIterator for IntoOnes<S> {}
+
+impl<S: BitStore> core::iter::FusedIterator for IntoOnes<S> {}
+
+/// An owning iterator over the indices of the unset bits of a store, in
+/// ascending order.
+///
+/// Like [Ones], it reads the store one word at a time, so full words are
+/// skipped without looking at their bits.
+#[derive(Debug, Clone)]
+pub struct IntoZeros<S: BitStore> {
+	bits: S,
+	// Words that haven't been loaded into `front` or `back` yet.
+	words: ops::Range<u32>,
+	front: WordOnes<S::Word>,
+	back: WordOnes<S::Word>,
+}
+
+impl<S: BitStore> From<S> for IntoZeros<S> {
+	#[inline]
+	fn from(bits: S) -> Self {
+		Self::new(bits)
+	}
+}
+
+impl<S: BitStore> IntoZeros<S> {
+	#[inline]
+	pub const fn new(bits: S) -> Self {
+		Self {
+			bits,
+			words: 0..S::WORDS,
+			front: WordOnes::new(<S::Word as store::BitStoreConst>::EMPTY, 0),
+			back: WordOnes::new(<S::Word as store::BitStoreConst>::EMPTY, 0),
+		}
+	}
+
+	#[inline]
+	fn load(&self, index: u32) -> WordOnes<S::Word> {
+		// SAFETY: `words` only contains indices in range 0..S::WORDS.
+		let word = unsafe { self.bits.word(index) };
+		WordOnes::new(!word, index * <S::Word as BitStore>::BITS)
+	}
+}
+
+impl<S: BitStore> Iterator for IntoZeros<S> {
+	type Item = u32;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some(index) = self.front.next() {
+				return Some(index);
+			}
+
+			match self.words.next() {
+				Some(i) => self.front = self.load(i),
+				None => return self.back.next(),
+			}
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let loaded = self.front.word.count_ones() + self.back.word.count_ones();
+		let unloaded = self
+			.words
+			.clone()
+			.map(|i| self.load(i).word.count_ones())
+			.sum::<u32>();
+		let len = (loaded + unloaded) as usize;
+		(len, Some(len))
+	}
+}
+
+impl<S: BitStore> DoubleEndedIterator for IntoZeros<S> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some(index) = self.back.next_back() {
+				return Some(index);
+			}
+
+			match self.words.next_back() {
+				Some(i) => self.back = self.load(i),
+				None => return self.front.next_back(),
+			}
+		}
+	}
+}
+
+impl<S: BitStore> ExactSizeIterator for IntoZeros<S> {}
+
+impl<S: BitStore> core::iter::FusedIterator for IntoZeros<S> {}
+
+/// An iterator over the maximal runs of consecutive set bits of a store, as
+/// half-open ranges in ascending order.
+#[derive(Debug, Clone)]
+pub struct Runs<S: BitStore> {
+	bits: S,
+	cursor: u32,
+}
+
+impl<S: BitStore> From<S> for Runs<S> {
+	#[inline]
+	fn from(bits: S) -> Self {
+		Self::new(bits)
+	}
+}
+
+impl<S: BitStore> Runs<S> {
+	#[inline]
+	pub const fn new(bits: S) -> Self {
+		Self { bits, cursor: 0 }
+	}
+}
+
+impl<S: BitStore> Iterator for Runs<S> {
+	type Item = ops::Range<u32>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let start = self.bits.next_one(self.cursor)?;
+		let end = store::scan_words(&self.bits, start, |w| !w).unwrap_or(S::BITS);
+		self.cursor = end;
+		Some(start..end)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let remaining = S::BITS.saturating_sub(self.cursor) as usize;
+		(0, Some(remaining.div_ceil(2)))
+	}
+}
+
+impl<S: BitStore> core::iter::FusedIterator for Runs<S> {}
+
+/// An iterator over the maximal runs of consecutive unset bits of a store,
+/// as half-open ranges in ascending order.
+#[derive(Debug, Clone)]
+pub struct Gaps<S: BitStore> {
+	bits: S,
+	cursor: u32,
+}
+
+impl<S: BitStore> From<S> for Gaps<S> {
+	#[inline]
+	fn from(bits: S) -> Self {
+		Self::new(bits)
+	}
+}
+
+impl<S: BitStore> Gaps<S> {
+	#[inline]
+	pub const fn new(bits: S) -> Self {
+		Self { bits, cursor: 0 }
+	}
+}
+
+impl<S: BitStore> Iterator for Gaps<S> {
+	type Item = ops::Range<u32>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let start = store::scan_words(&self.bits, self.cursor, |w| !w)?;
+		let end = self.bits.next_one(start).unwrap_or(S::BITS);
+		self.cursor = end;
+		Some(start..end)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let remaining = S::BITS.saturating_sub(self.cursor) as usize;
+		(0, Some(remaining.div_ceil(2)))
+	}
+}
+
+impl<S: BitStore> core::iter::FusedIterator for Gaps<S> {}
+
+/// A cursor that consumes the bits of a store from the lowest index up,
+/// keeping track of its position.
+///
+/// It is also an iterator over the remaining bits, so it can be used with
+/// [Iterator::by_ref] and other adapters.
+#[derive(Debug, Clone)]
+pub struct BitCursor<S: BitStore> {
+	bits: Bits<S>,
+}
+
+impl<S: BitStore> From<Bits<S>> for BitCursor<S> {
+	#[inline]
+	fn from(bits: Bits<S>) -> Self {
+		Self { bits }
+	}
+}
+
+impl<S: BitStore> BitCursor<S> {
+	#[inline]
+	pub const fn new(bits: S) -> Self {
+		Self {
+			bits: Bits::new(bits),
+		}
+	}
+
+	/// Returns the index of the next bit to be read.
+	#[inline]
+	pub fn position(&self) -> u32 {
+		self.bits.range.start
+	}
+
+	/// Returns the number of bits left to read.
+	#[inline]
+	pub fn remaining(&self) -> u32 {
+		self.bits.range.end - self.bits.range.start
+	}
+
+	/// Returns the next bit without consuming it.
+	#[inline]
+	pub fn peek(&self) -> Option<bool> {
+		let range = &self.bits.range;
+		// SAFETY: `range` is in bounds.
+		(range.start < range.end).then(|| unsafe { self.bits.bits.get(range.start) })
+	}
+
+	/// Reads up to `count` bits into the low bits of a `u32`, with the first
+	/// bit read ending up as bit 0. Stops early if the cursor runs out of bits.
+	///
+	/// # Panics
+	///
+	/// Panics if `count` is greater than 32.
+	pub fn take_bits(&mut self, count: u32) -> u32 {
+		if count > u32::BITS {
+			panic!(
+				"count out of bounds: the max is 32 but the count is {}",
+				count
+			);
+		}
+
+		let mut value = 0;
+		for (i, bit) in self.bits.by_ref().take(count as usize).enumerate() {
+			value |= (bit as u32) << i;
+		}
+
+		value
+	}
+}
+
+impl<S: BitStore> Iterator for BitCursor<S> {
+	type Item = bool;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.bits.next()
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.bits.size_hint()
+	}
+}
+
+impl<S: BitStore> ExactSizeIterator for BitCursor<S> {}
+
+impl<S: BitStore> core::iter::FusedIterator for BitCursor<S> {}
+
+/// An iterator over the indices of the set bits in a single word,
+/// offset by the index of the word's first bit.
+#[derive(Debug, Clone)]
+pub(crate) struct WordOnes<W: BitWord> {
+	word: W,
+	offset: u32,
+}
+
+impl<W: BitWord> WordOnes<W> {
+	#[inline]
+	pub(crate) const fn new(word: W, offset: u32) -> Self {
+		Self { word, offset }
+	}
+}
+
+impl<W: BitWord> Iterator for WordOnes<W> {
+	type Item = u32;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.word.is_empty() {
+			return None;
+		}
+
+		let index = self.word.trailing_zeros();
+		// SAFETY: the word is not empty, so `index` is in bounds.
+		unsafe { self.word.unset(index) };
+		Some(self.offset + index)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.word.count_ones() as usize;
+		(len, Some(len))
+	}
+}
+
+impl<W: BitWord> DoubleEndedIterator for WordOnes<W> {
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.word.is_empty() {
+			return None;
+		}
+
+		let index = W::BITS - 1 - self.word.leading_zeros();
+		// SAFETY: the word is not empty, so `index` is in bounds.
+		unsafe { self.word.unset(index) };
+		Some(self.offset + index)
+	}
+}
+
+impl<W: BitWord> core::iter::FusedIterator for WordOnes<W> {}
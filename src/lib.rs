@@ -28,12 +28,15 @@
 extern crate alloc;
 
 mod bit_fmt;
+pub mod cell;
+pub mod dynamic;
 pub mod iter;
 pub mod store;
 
+use core::cmp::Ordering;
 use core::fmt;
 use core::ops;
-use store::{BitStore, BitStoreConst, BitStoreMut, DefaultIsEmpty};
+use store::{BitStore, BitStoreConst, BitStoreMut, BitWord, DefaultIsEmpty, MsbFirst};
 
 /// A trait for collecting an iterator into a value as an operation
 /// that can fail.
@@ -54,6 +57,30 @@ impl<I: Iterator> TryCollectExt<I::Item> for I {
 	}
 }
 
+/// A trait for converting a bit store into a [BitSet].
+///
+/// # Examples
+///
+/// ```
+/// # use bitarr::{BitSet, ToBitSet};
+/// let bs = 0b1010u32.bitset();
+/// assert_eq!(bs, BitSet::from(0b1010u32));
+///
+/// let bs = [0u8; 4].bitset();
+/// assert_eq!(bs.len(), 32);
+/// assert!(bs.is_empty());
+/// ```
+pub trait ToBitSet: BitStore + Sized {
+	fn bitset(self) -> BitSet<Self>;
+}
+
+impl<S: BitStore> ToBitSet for S {
+	#[inline]
+	fn bitset(self) -> BitSet<Self> {
+		BitSet::from(self)
+	}
+}
+
 /// A compact data structure for storing bits
 ///
 /// # Examples
@@ -88,12 +115,79 @@ impl<S: BitStore> fmt::Debug for BitSet<S> {
 	}
 }
 
+/// Formats the `BitSet` in set-builder notation, listing the indices of the
+/// set bits in ascending order.
+///
+/// # Examples
+///
+/// ```
+/// # use bitarr::BitSet;
+/// assert_eq!(format!("{}", BitSet::from(0u8)), "{}");
+///
+/// let mut bs = BitSet::from([0u64; 2]);
+/// bs.set(0);
+/// bs.set(3);
+/// bs.set(7);
+/// assert_eq!(format!("{}", bs), "{0, 3, 7}");
+/// ```
+impl<S: BitStore> fmt::Display for BitSet<S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_set().entries(self.ones()).finish()
+	}
+}
+
 impl<S: BitStore> fmt::Binary for BitSet<S> {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		fmt::Binary::fmt(&bit_fmt::BinaryDisplay(&self.bits), f)
 	}
 }
 
+/// Formats the `BitSet` as a hexadecimal number, with the highest bits
+/// first like the [fmt::Binary] output. The `0x` prefix is only added with
+/// the alternate flag (`{:#x}`).
+///
+/// # Examples
+///
+/// ```
+/// # use bitarr::BitSet;
+/// assert_eq!(format!("{:#x}", BitSet::from(0xbeefu16)), "0xbeef");
+/// assert_eq!(format!("{:X}", BitSet::from([0xaau8, 0xbb])), "BBAA");
+/// ```
+impl<S: BitStore> fmt::LowerHex for BitSet<S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::LowerHex::fmt(&bit_fmt::HexDisplay(&self.bits), f)
+	}
+}
+
+/// Formats the `BitSet` as a hexadecimal number with upper case digits. See
+/// the [fmt::LowerHex] implementation.
+impl<S: BitStore> fmt::UpperHex for BitSet<S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::UpperHex::fmt(&bit_fmt::HexDisplay(&self.bits), f)
+	}
+}
+
+/// Formats the `BitSet` as an octal number, with the highest bits first like
+/// the [fmt::Binary] output. The `0o` prefix is only added with the
+/// alternate flag (`{:#o}`).
+///
+/// Digits are grouped from index 0 up, so when the number of bits isn't a
+/// multiple of 3 the first digit only holds the leftover high bits, e.g.,
+/// the top digit of a `u8` covers bits 6 and 7.
+///
+/// # Examples
+///
+/// ```
+/// # use bitarr::BitSet;
+/// assert_eq!(format!("{:#o}", BitSet::from(0xffu8)), "0o377");
+/// assert_eq!(format!("{:o}", BitSet::from(8u16)), "000010");
+/// ```
+impl<S: BitStore> fmt::Octal for BitSet<S> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Octal::fmt(&bit_fmt::OctalDisplay(&self.bits), f)
+	}
+}
+
 impl<S: BitStoreConst> BitSet<S> {
 	/// Creates a new `BitSet` with all bits set to 0.
 	/// This is equivalent to [BitSet::empty].
@@ -134,6 +228,88 @@ impl<S: BitStoreConst> BitSet<S> {
 	pub const fn full() -> Self {
 		Self { bits: S::FULL }
 	}
+
+	/// Creates a new `BitSet` with all bits set to 1.
+	/// This is equivalent to [BitSet::full], for use where "everything
+	/// enabled" is the natural starting point.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::<u8>::all_set();
+	/// assert!(bs.is_full());
+	/// ```
+	#[inline]
+	pub const fn all_set() -> Self {
+		Self::full()
+	}
+
+	/// Takes the bits out of the `BitSet`, leaving an empty `BitSet` in its
+	/// place. This is the `BitSet` counterpart of [Option::take].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0b0110u8);
+	/// let old = bs.take();
+	/// assert_eq!(old, BitSet::from(0b0110u8));
+	/// assert_eq!(bs, BitSet::empty());
+	/// ```
+	#[inline]
+	pub fn take(&mut self) -> Self {
+		core::mem::replace(self, Self::empty())
+	}
+}
+
+/// A `BitSet` whose [Default] value is full instead of empty, for
+/// configuration where leaving something unset should mean "everything on".
+///
+/// It dereferences to the wrapped `BitSet`.
+///
+/// # Examples
+///
+/// ```
+/// # use bitarr::FullDefault;
+/// let mut enabled = FullDefault::<u8>::default();
+/// assert!(enabled.is_full());
+///
+/// enabled.unset(3);
+/// assert_eq!(enabled.get(3), Some(false));
+/// ```
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FullDefault<S: BitStoreConst = usize>(pub BitSet<S>);
+
+impl<S: BitStoreConst> Default for FullDefault<S> {
+	#[inline]
+	fn default() -> Self {
+		Self(BitSet::full())
+	}
+}
+
+impl<S: BitStoreConst> From<BitSet<S>> for FullDefault<S> {
+	#[inline]
+	fn from(bs: BitSet<S>) -> Self {
+		Self(bs)
+	}
+}
+
+impl<S: BitStoreConst> ops::Deref for FullDefault<S> {
+	type Target = BitSet<S>;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl<S: BitStoreConst> ops::DerefMut for FullDefault<S> {
+	#[inline]
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.0
+	}
 }
 
 impl<S: BitStore> BitSet<S> {
@@ -178,6 +354,26 @@ impl<S: BitStore> BitSet<S> {
 		self.bits.get(index)
 	}
 
+	/// Creates a `BitSet` from a store that is used with a logical width of
+	/// `logical_bits`, returning `None` if any bit at or above `logical_bits`
+	/// is set.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// assert!(BitSet::from_checked(0b0111u8, 3).is_some());
+	/// assert!(BitSet::from_checked(0b1111u8, 3).is_none());
+	/// ```
+	#[inline]
+	pub fn from_checked(bits: S, logical_bits: u32) -> Option<Self> {
+		if logical_bits < S::BITS && bits.leading_zeros() < S::BITS - logical_bits {
+			return None;
+		}
+
+		Some(Self { bits })
+	}
+
 	/// Returns the number of bits in the `BitSet`.
 	///
 	/// # Examples
@@ -196,6 +392,29 @@ impl<S: BitStore> BitSet<S> {
 		S::BITS
 	}
 
+	/// Asserts at compile time that the `BitSet` has at least `N` bits.
+	///
+	/// The check is performed when the function is monomorphized, so a
+	/// store that is too narrow results in a compilation error rather
+	/// than a runtime panic.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// BitSet::<u16>::assert_min_bits::<16>();
+	/// BitSet::<[u8; 4]>::assert_min_bits::<20>();
+	/// ```
+	///
+	/// ```compile_fail
+	/// # use bitarr::BitSet;
+	/// BitSet::<u8>::assert_min_bits::<9>();
+	/// ```
+	#[inline]
+	pub const fn assert_min_bits<const N: u32>() {
+		let () = MinBits::<S, N>::ASSERT;
+	}
+
 	/// Returns `true` if the `BitSet` is empty, i.e., all bits are unset.
 	///
 	/// # Examples
@@ -240,714 +459,5039 @@ impl<S: BitStore> BitSet<S> {
 		!self.bits.is_empty()
 	}
 
-	/// Returns a borrowed iterator over the bits in the `BitSet`.
-	#[inline]
-	pub const fn iter(&self) -> iter::Bits<&S> {
-		iter::Bits::new(&self.bits)
-	}
-
-	/// Returns an iterator over the indices of the set bits in the `BitSet`.
-	pub fn ones(&self) -> impl Iterator<Item = u32> + DoubleEndedIterator + '_ {
-		self
-			.iter()
-			.enumerate()
-			.filter_map(|(i, b)| b.then_some(i as u32))
-	}
-
-	/// Returns an iterator over the indices of the set bits in the `BitSet`.
-	pub fn into_ones(self) -> impl Iterator<Item = u32> + DoubleEndedIterator {
-		self
-			.into_iter()
-			.enumerate()
-			.filter_map(|(i, b)| b.then_some(i as u32))
-	}
-}
-
-impl<S: BitStore> IntoIterator for BitSet<S> {
-	type Item = bool;
-	type IntoIter = iter::Bits<S>;
-
-	#[inline]
-	fn into_iter(self) -> Self::IntoIter {
-		iter::Bits::new(self.bits)
-	}
-}
-
-impl<S: BitStoreMut> BitSet<S> {
-	/// Sets the bit at the specified index, and returns
-	/// original value.
+	/// Returns the number of bits that are set.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs = BitSet::from(0u8);
-	/// assert_eq!(bs.set(3), Some(false));
-	/// assert_eq!(bs.get(3), Some(true));
+	/// let bs = BitSet::from(0b1011u8);
+	/// assert_eq!(bs.count_ones(), 3);
 	/// ```
 	#[inline]
-	pub fn set(&mut self, index: u32) -> Option<bool> {
-		if index >= S::BITS {
-			None
-		} else {
-			// SAFETY: The index is in bounds
-			unsafe {
-				let old = self.bits.get(index);
-				self.bits.set(index);
-				Some(old)
-			}
-		}
+	pub fn count_ones(&self) -> u32 {
+		self.bits.count_ones()
 	}
 
-	/// Sets the bit at the specified index without checking
-	/// that the index is in bounds, and returns original
-	/// value.
-	///
-	/// # Safety
-	/// Using an index that is out of bounds may lead to
-	/// undefined behavior.
+	/// Returns the number of bits that are unset.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs = BitSet::from(0u8);
-	/// assert_eq!(unsafe { bs.set_unchecked(3) }, false);
-	/// assert_eq!(bs.get(3), Some(true));
+	/// let bs = BitSet::from([0b0000_0111u8, 0b1000_0000]);
+	/// assert_eq!(bs.count_zeros(), 12);
 	/// ```
 	#[inline]
-	pub unsafe fn set_unchecked(&mut self, index: u32) -> bool {
-		let old = self.bits.get(index);
-		self.bits.set(index);
-		old
+	pub fn count_zeros(&self) -> u32 {
+		self.bits.count_zeros()
 	}
 
-	/// Unsets the bit at the specified index, and returns
-	/// original value.
+	/// Returns the number of set bits at indices below `index`.
+	///
+	/// If `index` is out of bounds, all set bits are counted.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs = BitSet::from(0u8);
-	/// bs.negate();
-	/// assert_eq!(bs.unset(3), Some(true));
-	/// assert_eq!(bs.get(3), Some(false));
+	/// let bs = BitSet::from([0b1011_0110u8, 0b0000_0001]);
+	/// assert_eq!(bs.rank(0), 0);
+	/// assert_eq!(bs.rank(3), 2);
+	/// assert_eq!(bs.rank(8), 5);
+	/// assert_eq!(bs.rank(16), 6);
 	/// ```
 	#[inline]
-	pub fn unset(&mut self, index: u32) -> Option<bool> {
-		if index >= S::BITS {
-			None
-		} else {
-			// SAFETY: The index is in bounds
-			unsafe {
-				let old = self.bits.get(index);
-				self.bits.unset(index);
-				Some(old)
-			}
+	pub fn rank(&self, index: u32) -> u32 {
+		// SAFETY: the index is clamped to 0..=S::BITS.
+		unsafe { self.bits.rank(index.min(S::BITS)) }
+	}
+
+	/// Panics if `end` is past the last bit, for methods taking a range.
+	#[track_caller]
+	#[inline]
+	fn check_range_end(end: u32) {
+		if end > S::BITS {
+			panic!(
+				"range end out of bounds: the len is {} but the end is {}",
+				S::BITS,
+				end
+			);
 		}
 	}
 
-	/// Unsets the bit at the specified index without checking
-	/// that the index is in bounds, and returns original
-	/// value.
+	/// Returns the number of set bits in `range`.
 	///
-	/// # Safety
-	/// Using an index that is out of bounds may lead to
-	/// undefined behavior.
+	/// # Panics
+	/// Panics if `range.start` is greater than `range.end`, or if
+	/// `range.end` is greater than the number of bits.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs = BitSet::from(0u8);
-	/// bs.negate();
-	/// assert_eq!(unsafe { bs.unset_unchecked(3) }, true);
-	/// assert_eq!(bs.get(3), Some(false));
+	/// let bs = BitSet::from([0b1011_0110u8, 0b0000_0001]);
+	/// assert_eq!(bs.count_ones_in_range(2..9), 5);
+	/// assert_eq!(bs.count_ones_in_range(5..5), 0);
 	/// ```
 	#[inline]
-	pub unsafe fn unset_unchecked(&mut self, index: u32) -> bool {
-		let old = self.bits.get(index);
-		self.bits.unset(index);
-		old
+	pub fn count_ones_in_range(&self, range: ops::Range<u32>) -> u32 {
+		if range.start > range.end {
+			panic!(
+				"range start is greater than range end: the start is {} but the end is {}",
+				range.start, range.end
+			);
+		}
+
+		Self::check_range_end(range.end);
+
+		// SAFETY: both ends of the range are in range 0..=S::BITS.
+		unsafe { self.bits.rank(range.end) - self.bits.rank(range.start) }
 	}
 
-	/// Changes the bit at the specified index to `value`, and returns
-	/// original value.
+	/// Returns the number of consecutive bits equal to `value`, counting down
+	/// from the highest index.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs = BitSet::from(0u8);
-	/// assert_eq!(bs.change(3, true), Some(false));
-	/// assert_eq!(bs.get(3), Some(true));
-	/// assert_eq!(bs.change(3, false), Some(true));
-	/// assert_eq!(bs.get(3), Some(false));
+	/// let bs = BitSet::from(0b1110_0100u8);
+	/// assert_eq!(bs.leading_run(true), 3);
+	/// assert_eq!(bs.leading_run(false), 0);
 	/// ```
 	#[inline]
-	pub fn change(&mut self, index: u32, value: bool) -> Option<bool> {
+	pub fn leading_run(&self, value: bool) -> u32 {
 		if value {
-			self.set(index)
+			self.bits.leading_ones()
 		} else {
-			self.unset(index)
+			self.bits.leading_zeros()
 		}
 	}
 
-	/// Changes the bit at the specified index to `value` without checking
-	/// that the index is in bounds, and returns original value.
-	///
-	/// # Safety
-	/// Using an index that is out of bounds may lead to
-	/// undefined behavior.
+	/// Returns the number of consecutive bits equal to `value`, counting up
+	/// from index 0.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs = BitSet::from(0u8);
-	/// assert_eq!(unsafe { bs.change_unchecked(3, true) }, false);
-	/// assert_eq!(bs.get(3), Some(true));
-	/// assert_eq!(unsafe { bs.change_unchecked(3, false) }, true);
-	/// assert_eq!(bs.get(3), Some(false));
+	/// let bs = BitSet::from(0b1110_0100u8);
+	/// assert_eq!(bs.trailing_run(true), 0);
+	/// assert_eq!(bs.trailing_run(false), 2);
 	/// ```
 	#[inline]
-	pub unsafe fn change_unchecked(&mut self, index: u32, value: bool) -> bool {
+	pub fn trailing_run(&self, value: bool) -> u32 {
 		if value {
-			self.set_unchecked(index)
+			self.bits.trailing_ones()
 		} else {
-			self.unset_unchecked(index)
+			self.bits.trailing_zeros()
 		}
 	}
 
-	/// Performs the union of two `BitSet`s, modifying `self` in place.
+	/// Returns `true` if the set bits are exactly the indices `0..k` for
+	/// some `k`, i.e., all set bits are below all unset bits.
+	///
+	/// Both the empty and the full `BitSet` are prefixes.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs1 = BitSet::from(0u16);
-	/// let mut bs2 = BitSet::from(0o16);
+	/// assert!(BitSet::from(0b0000_1111u8).is_prefix());
+	/// assert!(!BitSet::from(0b0001_0111u8).is_prefix());
+	/// ```
+	#[inline]
+	pub fn is_prefix(&self) -> bool {
+		self.bits.trailing_ones() + self.bits.leading_zeros() == S::BITS
+	}
+
+	/// Returns `true` if the set bits are exactly the indices `k..BITS` for
+	/// some `k`, i.e., all set bits are above all unset bits.
 	///
-	/// bs1.set(3);
-	/// bs1.set(7);
+	/// Both the empty and the full `BitSet` are suffixes.
 	///
-	/// bs2.set(7);
-	/// bs2.set(9);
-	///
-	/// bs1.union_with(&bs2);
+	/// # Examples
 	///
-	/// assert_eq!(bs1.get(3), Some(true));
-	/// assert_eq!(bs1.get(7), Some(true));
-	/// assert_eq!(bs1.get(9), Some(true));
+	/// ```
+	/// # use bitarr::BitSet;
+	/// assert!(BitSet::from(0b1111_0000u8).is_suffix());
+	/// assert!(!BitSet::from(0b1110_1000u8).is_suffix());
 	/// ```
 	#[inline]
-	pub fn union_with(&mut self, other: &Self) {
-		self.bits.union_with(&other.bits);
+	pub fn is_suffix(&self) -> bool {
+		self.bits.leading_ones() + self.bits.trailing_zeros() == S::BITS
 	}
 
-	/// Performs the intersection of two `BitSet`s, modifying `self` in place.
+	/// Returns the low `count` bits of the set as an integer, LSB-first, so
+	/// that bit `i` of the set is bit `i` of the result. Bits past the end of
+	/// the set read as 0.
+	///
+	/// # Panics
+	/// Panics if `count` is greater than 32.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs1 = BitSet::from(0u16);
-	/// let mut bs2 = BitSet::from(0u16);
-	///
-	/// bs1.set(3);
-	/// bs1.set(7);
+	/// let bs = BitSet::from(0b1011_0110u8);
+	/// assert_eq!(bs.pack_low(4), 0b0110);
+	/// assert_eq!(bs.pack_low(12), 0b1011_0110);
+	/// ```
+	pub fn pack_low(&self, count: u32) -> u32 {
+		if count > 32 {
+			panic!(
+				"count out of bounds: the max is 32 but the count is {}",
+				count
+			);
+		}
+
+		(0..count.min(S::BITS)).fold(0, |value, i| {
+			// SAFETY: `i` is in range 0..S::BITS.
+			value | unsafe { self.bits.bit(i) } << i
+		})
+	}
+
+	/// Returns the smallest range containing all set bits, or `None` if the
+	/// `BitSet` is empty.
 	///
-	/// bs2.set(7);
-	/// bs2.set(9);
+	/// Unlike [BitSet::is_prefix] and friends, the set bits don't need to be
+	/// contiguous; any unset bits between them are part of the range.
 	///
-	/// bs1.intersect_with(&bs2);
+	/// # Examples
 	///
-	/// assert_eq!(bs1.get(3), Some(false));
-	/// assert_eq!(bs1.get(7), Some(true));
-	/// assert_eq!(bs1.get(9), Some(false));
+	/// ```
+	/// # use bitarr::BitSet;
+	/// assert_eq!(BitSet::from(0b0100_1000u8).bounding_range(), Some(3..7));
+	/// assert_eq!(BitSet::from(0u8).bounding_range(), None);
 	/// ```
 	#[inline]
-	pub fn intersect_with(&mut self, other: &Self) {
-		self.bits.intersect_with(&other.bits);
+	pub fn bounding_range(&self) -> Option<ops::Range<u32>> {
+		if self.bits.is_empty() {
+			return None;
+		}
+
+		Some(self.bits.trailing_zeros()..S::BITS - self.bits.leading_zeros())
 	}
 
-	/// Performs the difference of two `BitSet`s, modifying `self` in place.
+	/// Returns the indices of the lowest and highest set bits, or `None` if
+	/// the `BitSet` is empty.
+	///
+	/// Both are found in a single pass over the words, meeting at the
+	/// lowest non-empty word.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs1 = BitSet::from(0u16);
-	/// let mut bs2 = BitSet::from(0u16);
-	///
-	/// bs1.set(3);
-	/// bs1.set(7);
+	/// assert_eq!(BitSet::from([0b0100u8, 0, 0b0001]).extent(), Some((2, 16)));
+	/// assert_eq!(BitSet::from([0u8; 3]).extent(), None);
+	/// ```
+	pub fn extent(&self) -> Option<(u32, u32)> {
+		let word_bits = <S::Word as BitStore>::BITS;
+		// SAFETY: all word indices are in range 0..S::WORDS.
+		let word = |i| unsafe { self.bits.word(i) };
+		let first = (0..S::WORDS).find(|&i| !word(i).is_empty())?;
+		let last = (first..S::WORDS)
+			.rev()
+			.find(|&i| !word(i).is_empty())
+			.unwrap_or(first);
+
+		Some((
+			first * word_bits + word(first).trailing_zeros(),
+			last * word_bits + word_bits - 1 - word(last).leading_zeros(),
+		))
+	}
+
+	/// Returns the index of the lowest set bit, or `None` if the `BitSet` is
+	/// empty.
 	///
-	/// bs2.set(7);
-	/// bs2.set(9);
+	/// # Examples
 	///
-	/// bs1.difference_with(&bs2);
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from([0u64; 10]);
+	/// assert_eq!(bs.first_one(), None);
 	///
-	/// assert_eq!(bs1.get(3), Some(true));
-	/// assert_eq!(bs1.get(7), Some(false));
-	/// assert_eq!(bs1.get(9), Some(false));
+	/// bs.set(3);
+	/// bs.set(40);
+	/// assert_eq!(bs.first_one(), Some(3));
 	/// ```
 	#[inline]
-	pub fn difference_with(&mut self, other: &Self) {
-		self.bits.difference_with(&other.bits);
+	pub fn first_one(&self) -> Option<u32> {
+		let index = self.bits.trailing_zeros();
+		(index < S::BITS).then_some(index)
 	}
 
-	/// Performs the symmetric difference of two `BitSet`s, modifying `self` in place.
+	/// Returns the index of the highest set bit, or `None` if the `BitSet` is
+	/// empty.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs1 = BitSet::from(0u16);
-	/// let mut bs2 = BitSet::from(0u16);
-	///
-	/// bs1.set(3);
-	/// bs1.set(7);
-	///
-	/// bs2.set(7);
-	/// bs2.set(9);
-	///
-	/// bs1.symmetric_difference_with(&bs2);
+	/// let mut bs = BitSet::from([0u64; 10]);
+	/// assert_eq!(bs.last_one(), None);
 	///
-	/// assert_eq!(bs1.get(3), Some(true));
-	/// assert_eq!(bs1.get(7), Some(false));
-	/// assert_eq!(bs1.get(9), Some(true));
+	/// bs.set(3);
+	/// bs.set(40);
+	/// assert_eq!(bs.last_one(), Some(40));
 	/// ```
 	#[inline]
-	pub fn symmetric_difference_with(&mut self, other: &Self) {
-		self.bits.symmetric_difference_with(&other.bits);
+	pub fn last_one(&self) -> Option<u32> {
+		let leading = self.bits.leading_zeros();
+		(leading < S::BITS).then(|| S::BITS - 1 - leading)
 	}
 
-	/// Performs the negation of all the bits, modifying `self` in place.
+	/// Returns the index of the lowest unset bit, or `None` if the `BitSet`
+	/// is full.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs1 = BitSet::from(0u16);
+	/// let bs = BitSet::from(0b0010_0111u8);
+	/// assert_eq!(bs.first_zero(), Some(3));
+	/// assert_eq!(BitSet::<u8>::full().first_zero(), None);
+	/// ```
+	#[inline]
+	pub fn first_zero(&self) -> Option<u32> {
+		let index = self.bits.trailing_ones();
+		(index < S::BITS).then_some(index)
+	}
+
+	/// Returns the index of the highest unset bit, or `None` if the `BitSet`
+	/// is full.
 	///
-	/// bs1.set(3);
-	/// bs1.set(7);
-	/// bs1.negate();
+	/// # Examples
 	///
-	/// assert_eq!(bs1.get(3), Some(false));
-	/// assert_eq!(bs1.get(7), Some(false));
-	/// assert_eq!(bs1.get(9), Some(true));
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b1100_0111u8);
+	/// assert_eq!(bs.last_zero(), Some(5));
+	/// assert_eq!(BitSet::<u8>::full().last_zero(), None);
 	/// ```
 	#[inline]
-	pub fn negate(&mut self) {
-		self.bits.negate();
+	pub fn last_zero(&self) -> Option<u32> {
+		let leading = self.bits.leading_ones();
+		(leading < S::BITS).then(|| S::BITS - 1 - leading)
 	}
-}
 
-impl<S: BitStore> From<S> for BitSet<S> {
+	/// Returns the index of the lowest set bit at or after `from`, or `None`
+	/// if there is none.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b1010_0110u8);
+	/// assert_eq!(bs.next_set_bit(0), Some(1));
+	/// assert_eq!(bs.next_set_bit(3), Some(5));
+	/// assert_eq!(bs.next_set_bit(8), None);
+	/// ```
 	#[inline]
-	fn from(bits: S) -> Self {
-		Self { bits }
+	pub fn next_set_bit(&self, from: u32) -> Option<u32> {
+		self.bits.next_one(from)
 	}
-}
 
-impl<S: BitStore> ops::Index<u32> for BitSet<S> {
-	type Output = bool;
+	/// Returns the index of the `n`th set bit counting down from the highest
+	/// index, so `select_from_end(0)` is the highest set bit. Returns `None`
+	/// if fewer than `n + 1` bits are set.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b0101_1001u8);
+	/// assert_eq!(bs.select_from_end(0), Some(6));
+	/// assert_eq!(bs.select_from_end(2), Some(3));
+	/// assert_eq!(bs.select_from_end(4), None);
+	/// ```
+	pub fn select_from_end(&self, mut n: u32) -> Option<u32> {
+		let word_bits = <S::Word as BitStore>::BITS;
+		for i in (0..S::WORDS).rev() {
+			// SAFETY: `i` is in range 0..S::WORDS.
+			let mut word = unsafe { self.bits.word(i) };
+			let ones = word.count_ones();
+			if n >= ones {
+				n -= ones;
+				continue;
+			}
 
-	#[inline]
-	fn index(&self, index: u32) -> &Self::Output {
-		if index >= S::BITS {
-			panic!(
-				"index out of bounds: the len is {} but the index is {}",
-				S::BITS,
-				index
-			);
-		}
+			for _ in 0..n {
+				// SAFETY: `word` has more than `n` bits set, so it is not empty
+				// and its highest set bit is in range.
+				unsafe { word.unset(word_bits - 1 - word.leading_zeros()) };
+			}
 
-		// SAFETY: the index is in bounds
-		match unsafe { self.bits.get(index) } {
-			true => &true,
-			false => &false,
+			return Some(i * word_bits + word_bits - 1 - word.leading_zeros());
 		}
+
+		None
 	}
-}
 
-impl<S: BitStoreMut + Clone> BitSet<S> {
-	/// Performs the union of two `BitSet`s.
+	/// Returns `true` if the set bits are exactly the given `indices`, in any
+	/// order. Repeated indices are allowed, and indices that are out of
+	/// bounds never match.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs1 = BitSet::from(0u16);
-	/// let mut bs2 = BitSet::from(0o16);
-	///
-	/// bs1.set(3);
-	/// bs1.set(7);
+	/// let mut bs = BitSet::from(0u16);
+	/// bs.set(3);
+	/// bs.set(7);
+	/// assert!(bs.is_exactly(&[7, 3]));
+	/// assert!(!bs.is_exactly(&[3]));
+	/// ```
+	pub fn is_exactly(&self, indices: &[u32]) -> bool {
+		let mut distinct = 0;
+		for (i, &index) in indices.iter().enumerate() {
+			if self.get(index) != Some(true) {
+				return false;
+			}
+
+			if !indices[..i].contains(&index) {
+				distinct += 1;
+			}
+		}
+
+		distinct == self.bits.count_ones()
+	}
+
+	/// Compares the bits of two `BitSet`s from the highest index down to
+	/// the lowest, i.e., in the order they are written by the [fmt::Binary]
+	/// formatter.
 	///
-	/// bs2.set(7);
-	/// bs2.set(9);
+	/// Unlike the derived [Ord] implementation, which depends on how the
+	/// backing store orders itself (arrays compare their first element
+	/// first), this ordering only depends on the bits.
 	///
-	/// let bs3 = bs1.union(&bs2);
+	/// # Examples
 	///
-	/// assert_eq!(bs3.get(3), Some(true));
-	/// assert_eq!(bs3.get(7), Some(true));
-	/// assert_eq!(bs3.get(9), Some(true));
 	/// ```
-	#[inline]
-	pub fn union(&self, other: &Self) -> Self {
-		let mut bits = self.bits.clone();
-		bits.union_with(&other.bits);
-		Self { bits }
+	/// # use bitarr::BitSet;
+	/// # use core::cmp::Ordering;
+	/// let bs1 = BitSet::from([0b0000_0001u8, 0]);
+	/// let bs2 = BitSet::from([0u8, 0b0000_0001]);
+	/// assert_eq!(bs1.cmp(&bs2), Ordering::Greater);
+	/// assert_eq!(bs1.cmp_bits(&bs2), Ordering::Less);
+	/// ```
+	pub fn cmp_bits(&self, other: &Self) -> Ordering {
+		for i in (0..S::WORDS).rev() {
+			// SAFETY: `i` is in range 0..S::WORDS.
+			let (a, b) = unsafe { (self.bits.word(i), other.bits.word(i)) };
+			match a.cmp(&b) {
+				Ordering::Equal => continue,
+				ordering => return ordering,
+			}
+		}
+
+		Ordering::Equal
 	}
 
-	/// Performs the intersection of two `BitSet`s.
+	/// Returns the overlap coefficient of `self` and `other`, i.e., the
+	/// size of their intersection divided by the size of the smaller set.
+	///
+	/// Two empty sets are defined to have an overlap coefficient of `1.0`,
+	/// while an empty and a non-empty set have an overlap coefficient of `0.0`.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs1 = BitSet::from(0u16);
-	/// let mut bs2 = BitSet::from(0u16);
-	///
-	/// bs1.set(3);
-	/// bs1.set(7);
+	/// let bs1 = BitSet::from(0b0000_1111u8);
+	/// let bs2 = BitSet::from(0b0011_1100u8);
+	/// assert_eq!(bs1.overlap_coefficient(&bs2), 0.5);
+	/// ```
+	#[cfg(feature = "std")]
+	pub fn overlap_coefficient(&self, other: &Self) -> f64 {
+		let min = self.bits.count_ones().min(other.bits.count_ones());
+		if min == 0 {
+			return if self.is_empty() && other.is_empty() {
+				1.0
+			} else {
+				0.0
+			};
+		}
+
+		self.intersection_count(other) as f64 / min as f64
+	}
+
+	/// Returns the Sørensen–Dice coefficient of `self` and `other`, i.e.,
+	/// twice the size of their intersection divided by the sum of their sizes.
 	///
-	/// bs2.set(7);
-	/// bs2.set(9);
+	/// Two empty sets are defined to have a Dice coefficient of `1.0`.
 	///
-	/// let bs3 = bs1.intersection(&bs2);
+	/// # Examples
 	///
-	/// assert_eq!(bs3.get(3), Some(false));
-	/// assert_eq!(bs3.get(7), Some(true));
-	/// assert_eq!(bs3.get(9), Some(false));
 	/// ```
-	#[inline]
-	pub fn intersection(&self, other: &Self) -> Self {
-		let mut bits = self.bits.clone();
-		bits.intersect_with(&other.bits);
-		Self { bits }
+	/// # use bitarr::BitSet;
+	/// let bs1 = BitSet::from(0b0000_1111u8);
+	/// let bs2 = BitSet::from(0b0011_1100u8);
+	/// assert_eq!(bs1.dice(&bs2), 0.5);
+	/// ```
+	#[cfg(feature = "std")]
+	pub fn dice(&self, other: &Self) -> f64 {
+		let total = self.bits.count_ones() + other.bits.count_ones();
+		if total == 0 {
+			return 1.0;
+		}
+
+		(2 * self.intersection_count(other)) as f64 / total as f64
 	}
 
-	/// Performs the difference of two `BitSet`s.
+	/// Returns the number of bits set in both `self` and `other`, without
+	/// creating their intersection.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs1 = BitSet::from(0u16);
-	/// let mut bs2 = BitSet::from(0u16);
-	///
-	/// bs1.set(3);
-	/// bs1.set(7);
-	///
-	/// bs2.set(7);
-	/// bs2.set(9);
-	///
-	/// let bs3 = bs1.difference(&bs2);
-	///
-	/// assert_eq!(bs3.get(3), Some(true));
-	/// assert_eq!(bs3.get(7), Some(false));
-	/// assert_eq!(bs3.get(9), Some(false));
+	/// let bs1 = BitSet::from([0b1011_0110u8, 0b1111_0000]);
+	/// let bs2 = BitSet::from([0b0000_1111u8, 0b0011_1100]);
+	/// assert_eq!(bs1.intersection_count(&bs2), 4);
+	/// assert_eq!(
+	///     bs1.intersection_count(&bs2),
+	///     bs1.intersection(&bs2).count_ones()
+	/// );
 	/// ```
 	#[inline]
-	pub fn difference(&self, other: &Self) -> Self {
-		let mut bits = self.bits.clone();
-		bits.difference_with(&other.bits);
-		Self { bits }
+	pub fn intersection_count(&self, other: &Self) -> u32 {
+		self.bits.intersection_count(&other.bits)
 	}
 
-	/// Performs the symmetric difference of two `BitSet`s.
+	/// Returns the number of bits set in `self` or `other`, without creating
+	/// their union.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs1 = BitSet::from(0u16);
-	/// let mut bs2 = BitSet::from(0u16);
-	///
-	/// bs1.set(3);
-	/// bs1.set(7);
-	///
-	/// bs2.set(7);
-	/// bs2.set(9);
-	///
-	/// let bs3 = bs1.symmetric_difference(&bs2);
-	///
-	/// assert_eq!(bs3.get(3), Some(true));
-	/// assert_eq!(bs3.get(7), Some(false));
-	/// assert_eq!(bs3.get(9), Some(true));
+	/// let bs1 = BitSet::from(0b0000_1111u8);
+	/// let bs2 = BitSet::from(0b0011_1100u8);
+	/// assert_eq!(bs1.union_count(&bs2), 6);
 	/// ```
 	#[inline]
-	pub fn symmetric_difference(&self, other: &Self) -> Self {
-		let mut bits = self.bits.clone();
-		bits.symmetric_difference_with(&other.bits);
-		Self { bits }
+	pub fn union_count(&self, other: &Self) -> u32 {
+		self.count_ones_with(other, |a, b| a | b)
 	}
 
-	/// Performs the negation of all the bits.
+	/// Returns the number of bits set in `self` but not in `other`, without
+	/// creating their difference.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs1 = BitSet::from(0u16);
-	///
-	/// bs1.set(3);
-	/// bs1.set(7);
-	/// let bs2 = bs1.negation();
-	///
-	/// assert_eq!(bs2.get(3), Some(false));
-	/// assert_eq!(bs2.get(7), Some(false));
-	/// assert_eq!(bs2.get(9), Some(true));
+	/// let bs1 = BitSet::from(0b0000_1111u8);
+	/// let bs2 = BitSet::from(0b0011_1100u8);
+	/// assert_eq!(bs1.difference_count(&bs2), 2);
+	/// ```
 	#[inline]
-	pub fn negation(&self) -> Self {
-		let mut bits = self.bits.clone();
-		bits.negate();
-		Self { bits }
+	pub fn difference_count(&self, other: &Self) -> u32 {
+		self.count_ones_with(other, |a, b| a & !b)
 	}
 
-	/// Returns `true` if `self` is a subset of `other`,
-	/// i.e., every bit in `self` is set in `other`.
+	/// Returns the number of bits set in exactly one of `self` and `other`,
+	/// without creating their symmetric difference.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs1 = BitSet::from(0u8);
-	/// let mut bs2 = BitSet::from(0u8);
+	/// let bs1 = BitSet::from(0b0000_1111u8);
+	/// let bs2 = BitSet::from(0b0011_1100u8);
+	/// assert_eq!(bs1.symmetric_difference_count(&bs2), 4);
+	/// ```
+	#[inline]
+	pub fn symmetric_difference_count(&self, other: &Self) -> u32 {
+		self.count_ones_with(other, |a, b| a ^ b)
+	}
+
+	/// Returns the Hamming distance between `self` and `other`, i.e., the
+	/// number of indices at which their bits differ.
 	///
-	/// bs1.set(7);
+	/// This is the same as [BitSet::symmetric_difference_count].
 	///
-	/// bs2.set(3);
-	/// bs2.set(7);
+	/// # Examples
 	///
-	/// assert!(bs1.is_subset(&bs2));
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs1 = BitSet::from(0b1010_0000_1111_0000u16);
+	/// let bs2 = BitSet::from(0b1000_0000_1110_0001u16);
+	/// assert_eq!(bs1.hamming_distance(&bs2), 3);
 	/// ```
 	#[inline]
-	pub fn is_subset(&self, other: &Self) -> bool {
-		self.difference(other).is_empty()
+	pub fn hamming_distance(&self, other: &Self) -> u32 {
+		self.symmetric_difference_count(other)
 	}
 
-	/// Returns `true` if `self` is a superset of `other`,
-	/// i.e., every bit in `other` is set in `self`.
+	/// Returns the Jaccard similarity of `self` and `other` as the unreduced
+	/// fraction `(intersection_count, union_count)`, leaving the division to
+	/// the caller.
+	///
+	/// Two empty sets give `(0, 0)`, which callers should treat as a
+	/// similarity of `1`.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs1 = BitSet::from(0u8);
-	/// let mut bs2 = BitSet::from(0u8);
-	///
-	/// bs1.set(3);
-	/// bs1.set(7);
-	///
-	/// bs2.set(7);
-	///
-	/// assert!(bs1.is_superset(&bs2));
+	/// let bs1 = BitSet::from(0b0000_1111u8);
+	/// let bs2 = BitSet::from(0b0011_1100u8);
+	/// assert_eq!(bs1.jaccard(&bs2), (2, 6));
 	/// ```
 	#[inline]
-	pub fn is_superset(&self, other: &Self) -> bool {
-		other.is_subset(self)
+	pub fn jaccard(&self, other: &Self) -> (u32, u32) {
+		(self.intersection_count(other), self.union_count(other))
 	}
 
-	/// Returns `true` if `self` and `other` have no common bits set,
-	/// i.e., their intersection is empty.
+	/// Returns the number of bits set in both `self` and `mask`, without
+	/// creating their intersection.
 	///
 	/// # Examples
 	///
 	/// ```
 	/// # use bitarr::BitSet;
-	/// let mut bs1 = BitSet::from(0u8);
-	/// let mut bs2 = BitSet::from(0u8);
-	///
-	/// bs1.set(3);
-	///
-	/// bs2.set(7);
-	///
-	/// assert!(bs1.is_disjoint(&bs2));
+	/// let bs = BitSet::from(0b1011_0110u8);
+	/// let mask = BitSet::from(0b0000_1111u8);
+	/// assert_eq!(bs.count_ones_where(&mask), 2);
 	/// ```
 	#[inline]
-	pub fn is_disjoint(&self, other: &Self) -> bool {
-		self.intersection(other).is_empty()
+	pub fn count_ones_where(&self, mask: &Self) -> u32 {
+		self.intersection_count(mask)
 	}
-}
-
-impl<S: BitStoreMut + Clone> ops::Neg for BitSet<S> {
-	type Output = Self;
 
+	/// Returns the number of set bits in the word-wise combination of `self`
+	/// and `other` by `f`, without creating an intermediate `BitSet`.
 	#[inline]
-	fn neg(self) -> Self::Output {
-		self.negation()
+	fn count_ones_with(&self, other: &Self, f: impl Fn(S::Word, S::Word) -> S::Word) -> u32 {
+		store::count_ones_with(&self.bits, &other.bits, f)
 	}
-}
-
-impl<S: BitStoreMut + Clone> ops::BitAnd for BitSet<S> {
-	type Output = Self;
 
+	/// Returns a borrowed iterator over the bits in the `BitSet`.
 	#[inline]
-	fn bitand(self, rhs: Self) -> Self::Output {
-		self.intersection(&rhs)
+	pub const fn iter(&self) -> iter::Bits<&S> {
+		iter::Bits::new(&self.bits)
 	}
-}
 
-impl<S: BitStoreMut> ops::BitAndAssign for BitSet<S> {
+	/// Returns a cursor that reads the bits of the `BitSet` from the lowest
+	/// index up.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b1011_0110u8);
+	/// let mut cursor = bs.cursor();
+	/// assert_eq!(cursor.take_bits(4), 0b0110);
+	/// assert_eq!(cursor.position(), 4);
+	/// assert_eq!(cursor.peek(), Some(true));
+	/// assert_eq!(cursor.take_bits(4), 0b1011);
+	/// assert_eq!(cursor.remaining(), 0);
+	/// ```
 	#[inline]
-	fn bitand_assign(&mut self, rhs: Self) {
-		self.intersect_with(&rhs);
+	pub const fn cursor(&self) -> iter::BitCursor<&S> {
+		iter::BitCursor::new(&self.bits)
 	}
-}
-
-impl<S: BitStoreMut + Clone> ops::BitOr for BitSet<S> {
-	type Output = Self;
 
+	/// Returns an iterator over the indices of the set bits in the `BitSet`.
 	#[inline]
-	fn bitor(self, rhs: Self) -> Self::Output {
-		self.union(&rhs)
+	pub const fn ones(&self) -> iter::Ones<&S> {
+		iter::Ones::new(&self.bits)
 	}
-}
 
-impl<S: BitStoreMut> ops::BitOrAssign for BitSet<S> {
+	/// Returns an iterator over the indices of the unset bits in the
+	/// `BitSet`, in ascending order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b1010u8);
+	/// assert!(bs.zeros().eq([0, 2, 4, 5, 6, 7]));
+	/// assert!(bs.zeros().rev().eq([7, 6, 5, 4, 2, 0]));
+	/// ```
 	#[inline]
-	fn bitor_assign(&mut self, rhs: Self) {
-		self.union_with(&rhs);
+	pub const fn zeros(&self) -> iter::IntoZeros<&S> {
+		iter::IntoZeros::new(&self.bits)
 	}
-}
-
-impl<S: BitStoreMut + Clone> ops::BitXor for BitSet<S> {
-	type Output = Self;
 
-	#[inline]
-	fn bitxor(self, rhs: Self) -> Self::Output {
-		self.symmetric_difference(&rhs)
+	/// Returns an iterator over the indices of the bits that are set in
+	/// both `self` and `other`, in ascending order.
+	///
+	/// The intersection is computed one word at a time, so no intermediate
+	/// `BitSet` is created.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs1 = BitSet::from([0b1010_1010u8, 0b0000_1111]);
+	/// let bs2 = BitSet::from([0b1111_0000u8, 0b0011_1100]);
+	/// assert!(bs1.common_ones(&bs2).eq([5, 7, 10, 11]));
+	/// ```
+	pub fn common_ones<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = u32> + 'a {
+		(0..S::WORDS).flat_map(move |i| {
+			// SAFETY: `i` is in range 0..S::WORDS.
+			let word = unsafe { self.bits.word(i) & other.bits.word(i) };
+			iter::WordOnes::new(word, i * <S::Word as BitStore>::BITS)
+		})
 	}
-}
 
-impl<S: BitStoreMut> ops::BitXorAssign for BitSet<S> {
-	#[inline]
-	fn bitxor_assign(&mut self, rhs: Self) {
-		self.symmetric_difference_with(&rhs);
+	/// Returns an iterator over the indices where `self` and `other` differ,
+	/// in ascending order, together with the value of the bit in `self`.
+	///
+	/// The differences are found one word at a time, so equal stretches of
+	/// the sets are skipped cheaply.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let new = BitSet::from(0b0110u8);
+	/// let old = BitSet::from(0b0011u8);
+	/// assert!(new.diff(&old).eq([(0, false), (2, true)]));
+	/// ```
+	pub fn diff<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = (u32, bool)> + 'a {
+		let word_bits = <S::Word as BitStore>::BITS;
+		(0..S::WORDS).flat_map(move |i| {
+			// SAFETY: `i` is in range 0..S::WORDS.
+			let (word, changed) = unsafe {
+				let word = self.bits.word(i);
+				(word, word ^ other.bits.word(i))
+			};
+
+			iter::WordOnes::new(changed, 0).map(move |bit| {
+				// SAFETY: `bit` is in range 0..word_bits.
+				(i * word_bits + bit, unsafe { word.get(bit) })
+			})
+		})
 	}
-}
 
-impl<S: BitStoreMut + Clone> ops::Sub for BitSet<S> {
-	type Output = Self;
+	/// Returns an iterator over the indices of the set bits at or after
+	/// `start`, in ascending order. Unset bits are skipped a word at a time,
+	/// so this is cheap to use to resume an earlier iteration.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b1010_0110u8);
+	/// assert!(bs.ones_from(3).eq([5, 7]));
+	/// assert!(bs.ones_from(8).eq([]));
+	/// ```
+	pub fn ones_from(&self, start: u32) -> impl Iterator<Item = u32> + '_ {
+		let mut cursor = start;
+		core::iter::from_fn(move || {
+			let index = self.bits.next_one(cursor)?;
+			cursor = index + 1;
+			Some(index)
+		})
+	}
 
+	/// Returns an iterator over the maximal runs of consecutive set bits in
+	/// the `BitSet`, as half-open ranges in ascending order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b1100_1110u8);
+	/// assert!(bs.runs().eq([1..4, 6..8]));
+	/// ```
 	#[inline]
-	fn sub(self, rhs: Self) -> Self::Output {
-		self.difference(&rhs)
+	pub const fn runs(&self) -> iter::Runs<&S> {
+		iter::Runs::new(&self.bits)
 	}
-}
 
-impl<S: BitStoreMut> ops::SubAssign for BitSet<S> {
+	/// Returns an iterator over the maximal runs of consecutive unset bits
+	/// in the `BitSet`, as half-open ranges in ascending order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b1100_1110u8);
+	/// assert!(bs.gaps().eq([0..1, 4..6]));
+	/// ```
 	#[inline]
-	fn sub_assign(&mut self, rhs: Self) {
-		self.difference_with(&rhs);
+	pub const fn gaps(&self) -> iter::Gaps<&S> {
+		iter::Gaps::new(&self.bits)
 	}
-}
-
-#[derive(Debug, Clone)]
-pub struct IndexRangeError<T: fmt::Debug + fmt::Display> {
-	index: T,
-	arr_len: u32,
-}
 
-impl<T: fmt::Debug + fmt::Display> fmt::Display for IndexRangeError<T> {
-	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(
-			f,
-			"index {} out of range for array of length {}",
-			self.index, self.arr_len
-		)
+	/// Returns an iterator over the maximal runs of consecutive set bits in
+	/// the `BitSet`. This is the same as [BitSet::runs].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b1100_1110u8);
+	/// assert!(bs.one_ranges().eq([1..4, 6..8]));
+	/// ```
+	#[inline]
+	pub const fn one_ranges(&self) -> iter::Runs<&S> {
+		self.runs()
 	}
-}
 
-impl<T: fmt::Debug + fmt::Display> std::error::Error for IndexRangeError<T> {}
+	/// Returns the number of adjacent pairs of bits `(i, i + 1)` that differ,
+	/// i.e., the number of `0 → 1` and `1 → 0` edges in the `BitSet`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// assert_eq!(BitSet::from(0b0000u8).transitions(), 0);
+	/// assert_eq!(BitSet::from(0b1010u8).transitions(), 4);
+	/// assert_eq!(BitSet::from(0b1111_0000u8).transitions(), 1);
+	/// ```
+	pub fn transitions(&self) -> u32 {
+		let word_bits = <S::Word as BitStore>::BITS;
+		let mut count = 0;
+		let mut previous_top = None;
+		for i in 0..S::WORDS {
+			// SAFETY: `i` is in range 0..S::WORDS, and the bit indices are in
+			// range 0..word_bits.
+			let (word, low, top) = unsafe {
+				let word = self.bits.word(i);
+				(word, word.get(0), word.get(word_bits - 1))
+			};
 
-impl<T: fmt::Debug + fmt::Display> IndexRangeError<T> {
-	#[cold]
-	fn new_err<R>(index: T, arr_len: u32) -> Result<R, Self> {
-		Err(Self { index, arr_len })
-	}
-}
+			// Bit `j` of `edges` is set if bits `j` and `j + 1` differ. The
+			// top bit is compared against the next word instead.
+			let edges = (word ^ word.shr_word(1)).shl_word(1).shr_word(1);
+			count += edges.count_ones();
+			if previous_top.is_some_and(|top| top != low) {
+				count += 1;
+			}
 
-macro_rules! impl_from_iter {
-	($t:ty) => {
-		impl<S: BitStoreMut + BitStoreConst> TryFromIterator<$t> for BitSet<S> {
-			type Error = IndexRangeError<$t>;
+			previous_top = Some(top);
+		}
 
-			fn try_from_iter<I: IntoIterator<Item = $t>>(iter: I) -> Result<Self, Self::Error> {
-				let mut bits = S::EMPTY;
-				for i in iter {
-					match u32::try_from(i) {
-						Ok(i) if i < S::BITS => {
-							// SAFETY: `i` is in range.
-							unsafe { bits.set(i) };
-						}
-						_ => return IndexRangeError::new_err(i, S::BITS),
-					}
-				}
+		count
+	}
 
-				Ok(Self { bits })
+	/// Returns statistics about the runs of equal bits in the `BitSet`,
+	/// computed in a single pass.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let stats = BitSet::from(0b1100_1110u8).run_stats();
+	/// assert_eq!(stats.runs, 4);
+	/// assert_eq!(stats.one_runs, 2);
+	/// assert_eq!(stats.longest_one_run, 3);
+	/// assert_eq!(stats.longest_zero_run, 2);
+	/// ```
+	pub fn run_stats(&self) -> RunStats {
+		let mut stats = RunStats::default();
+		let zero_run = |stats: &mut RunStats, len: u32| {
+			if len > 0 {
+				stats.zero_runs += 1;
+				stats.longest_zero_run = stats.longest_zero_run.max(len);
 			}
-		}
+		};
 
-		impl<S: BitStoreMut + BitStoreConst> FromIterator<$t> for BitSet<S> {
-			fn from_iter<I: IntoIterator<Item = $t>>(iter: I) -> Self {
-				match Self::try_from_iter(iter) {
-					Ok(v) => v,
-					Err(e) => panic!("{}", e),
-				}
-			}
+		let mut end = 0;
+		for range in self.runs() {
+			zero_run(&mut stats, range.start - end);
+			stats.one_runs += 1;
+			stats.longest_one_run = stats.longest_one_run.max(range.end - range.start);
+			end = range.end;
 		}
-	};
-}
-
-impl_from_iter!(u64);
-impl_from_iter!(u32);
-impl_from_iter!(u16);
-impl_from_iter!(u8);
-impl_from_iter!(usize);
 
-#[cfg(test)]
-mod tests {
-	use super::*;
+		zero_run(&mut stats, S::BITS - end);
+		stats.runs = stats.one_runs + stats.zero_runs;
+		stats
+	}
 
-	#[test]
-	fn union() {
-		let mut bs1 = BitSet::from(0u16);
-		let mut bs2 = BitSet::from(0u16);
+	/// Returns an iterator over the set bits as `(high, low)` pairs, where
+	/// `high` is the index of the 16-bit chunk the bit falls in, and `low` is
+	/// the offset of the bit within that chunk.
+	///
+	/// The pairs are in ascending order, so bits are grouped by chunk, which
+	/// is the shape expected by chunked sparse-set serializers.
+	///
+	/// # Panics
+	/// Panics when it reaches a set bit whose chunk index does not fit in a
+	/// `u16`, that is, a bit at index `2^20` or above.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0u32);
+	/// bs.set(3);
+	/// bs.set(18);
+	/// assert!(bs.export_chunks().eq([(0, 3), (1, 2)]));
+	/// ```
+	pub fn export_chunks(&self) -> impl Iterator<Item = (u16, u16)> + '_ {
+		self.ones().map(|i| {
+			let high = u16::try_from(i / 16).expect("bit index out of range for 16-bit chunks");
+			(high, (i % 16) as u16)
+		})
+	}
 
-		bs1.set(3);
-		bs1.set(7);
+	/// Returns the indices of the set bits as an array, in ascending order, if
+	/// exactly `N` bits are set. Returns `None` otherwise.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b0100_1010u8);
+	/// assert_eq!(bs.ones_array::<3>(), Some([1, 3, 6]));
+	/// assert_eq!(bs.ones_array::<2>(), None);
+	/// ```
+	pub fn ones_array<const N: usize>(&self) -> Option<[u32; N]> {
+		if self.bits.count_ones() as usize != N {
+			return None;
+		}
 
-		bs2.set(7);
-		bs2.set(9);
+		let mut ones = self.ones();
+		// `ones` yields exactly `N` indices, so the fallback is never used.
+		Some(core::array::from_fn(|_| ones.next().unwrap_or_default()))
+	}
 
-		bs1.union_with(&bs2);
+	/// Returns the first `N` bits as an array of lanes, so that `mask[i]` is
+	/// the bit at index `i`. Lanes past the end of the `BitSet` are `false`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b0000_0101u8);
+	/// assert_eq!(bs.lane_mask::<4>(), [true, false, true, false]);
+	/// ```
+	pub fn lane_mask<const N: usize>(&self) -> [bool; N] {
+		core::array::from_fn(|i| {
+			// SAFETY: `i` is checked to be in range before reading it.
+			u32::try_from(i).is_ok_and(|i| i < S::BITS && unsafe { self.bits.get(i) })
+		})
+	}
 
-		assert!(bs1[3]);
-		assert!(bs1[7]);
-		assert!(bs1[9]);
+	/// Returns all the bits as an array, LSB-first, if `N` is exactly the
+	/// number of bits in the `BitSet`. Returns `None` otherwise.
+	///
+	/// Use [BitSet::lane_mask] to get an array of any length.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b1000_0101u8);
+	/// assert_eq!(
+	///     bs.to_bool_array::<8>(),
+	///     Some([true, false, true, false, false, false, false, true])
+	/// );
+	/// assert_eq!(bs.to_bool_array::<4>(), None);
+	/// ```
+	#[inline]
+	pub fn to_bool_array<const N: usize>(&self) -> Option<[bool; N]> {
+		(N == S::BITS as usize).then(|| self.lane_mask())
 	}
 
-	#[test]
-	fn intersection() {
+	/// Reinterprets the `BitSet` so that index 0 is its highest bit.
+	///
+	/// The underlying bits are unchanged, only the mapping of indices used by
+	/// later calls differs, so e.g. the number of set bits stays the same.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b0000_0001u8).msb_first();
+	/// assert_eq!(bs.get(7), Some(true));
+	/// assert_eq!(bs.lsb_first(), BitSet::from(0b0000_0001u8));
+	/// ```
+	#[inline]
+	pub fn msb_first(self) -> BitSet<MsbFirst<S>> {
+		BitSet::from(MsbFirst(self.bits))
+	}
+
+	/// Returns an iterator over the indices of the set bits in the `BitSet`.
+	#[inline]
+	pub fn into_ones(self) -> iter::IntoOnes<S> {
+		iter::IntoOnes::new(self.bits)
+	}
+
+	/// Returns an iterator over the indices of the unset bits in the
+	/// `BitSet`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b1010u8);
+	/// assert!(bs.into_zeros().eq([0, 2, 4, 5, 6, 7]));
+	/// ```
+	#[inline]
+	pub fn into_zeros(self) -> iter::IntoZeros<S> {
+		iter::IntoZeros::new(self.bits)
+	}
+
+	/// Returns an iterator over the elements of the `BitSet` viewed as a set,
+	/// i.e., the indices of the set bits, in ascending order.
+	///
+	/// Note that iterating over a `BitSet` directly (through [IntoIterator])
+	/// yields a `bool` for every bit instead, set or not.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b0000_0110u8);
+	///
+	/// let mut bools = Vec::new();
+	/// for b in bs {
+	///     bools.push(b);
+	/// }
+	/// assert_eq!(bools, [false, true, true, false, false, false, false, false]);
+	///
+	/// let mut indices = Vec::new();
+	/// for i in bs.elements() {
+	///     indices.push(i);
+	/// }
+	/// assert_eq!(indices, [1, 2]);
+	/// ```
+	#[inline]
+	pub fn elements(self) -> iter::IntoOnes<S> {
+		self.into_ones()
+	}
+}
+
+struct MinBits<S, const N: u32>(core::marker::PhantomData<S>);
+
+impl<S: BitStore, const N: u32> MinBits<S, N> {
+	const ASSERT: () = assert!(S::BITS >= N, "bit store has fewer bits than required");
+}
+
+/// Statistics about the runs of equal bits in a `BitSet`, as returned by
+/// [BitSet::run_stats].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RunStats {
+	/// The total number of runs, i.e., `one_runs + zero_runs`.
+	pub runs: u32,
+	/// The number of maximal runs of set bits.
+	pub one_runs: u32,
+	/// The number of maximal runs of unset bits.
+	pub zero_runs: u32,
+	/// The length of the longest run of set bits.
+	pub longest_one_run: u32,
+	/// The length of the longest run of unset bits.
+	pub longest_zero_run: u32,
+}
+
+impl<S: BitStore> IntoIterator for BitSet<S> {
+	type Item = bool;
+	type IntoIter = iter::Bits<S>;
+
+	#[inline]
+	fn into_iter(self) -> Self::IntoIter {
+		iter::Bits::new(self.bits)
+	}
+}
+
+impl<S: BitStoreMut> BitSet<S> {
+	/// Sets the bit at the specified index, and returns
+	/// original value.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0u8);
+	/// assert_eq!(bs.set(3), Some(false));
+	/// assert_eq!(bs.get(3), Some(true));
+	/// ```
+	#[inline]
+	pub fn set(&mut self, index: u32) -> Option<bool> {
+		if index >= S::BITS {
+			None
+		} else {
+			// SAFETY: The index is in bounds
+			unsafe {
+				let old = self.bits.get(index);
+				self.bits.set(index);
+				Some(old)
+			}
+		}
+	}
+
+	/// Sets the bit at the specified index without checking
+	/// that the index is in bounds, and returns original
+	/// value.
+	///
+	/// # Safety
+	/// Using an index that is out of bounds may lead to
+	/// undefined behavior.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0u8);
+	/// assert_eq!(unsafe { bs.set_unchecked(3) }, false);
+	/// assert_eq!(bs.get(3), Some(true));
+	/// ```
+	#[inline]
+	pub unsafe fn set_unchecked(&mut self, index: u32) -> bool {
+		let old = self.bits.get(index);
+		self.bits.set(index);
+		old
+	}
+
+	/// Unsets the bit at the specified index, and returns
+	/// original value.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0u8);
+	/// bs.negate();
+	/// assert_eq!(bs.unset(3), Some(true));
+	/// assert_eq!(bs.get(3), Some(false));
+	/// ```
+	#[inline]
+	pub fn unset(&mut self, index: u32) -> Option<bool> {
+		if index >= S::BITS {
+			None
+		} else {
+			// SAFETY: The index is in bounds
+			unsafe {
+				let old = self.bits.get(index);
+				self.bits.unset(index);
+				Some(old)
+			}
+		}
+	}
+
+	/// Unsets the bit at the specified index without checking
+	/// that the index is in bounds, and returns original
+	/// value.
+	///
+	/// # Safety
+	/// Using an index that is out of bounds may lead to
+	/// undefined behavior.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0u8);
+	/// bs.negate();
+	/// assert_eq!(unsafe { bs.unset_unchecked(3) }, true);
+	/// assert_eq!(bs.get(3), Some(false));
+	/// ```
+	#[inline]
+	pub unsafe fn unset_unchecked(&mut self, index: u32) -> bool {
+		let old = self.bits.get(index);
+		self.bits.unset(index);
+		old
+	}
+
+	/// Changes the bit at the specified index to `value`, and returns
+	/// original value.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0u8);
+	/// assert_eq!(bs.change(3, true), Some(false));
+	/// assert_eq!(bs.get(3), Some(true));
+	/// assert_eq!(bs.change(3, false), Some(true));
+	/// assert_eq!(bs.get(3), Some(false));
+	/// ```
+	#[inline]
+	pub fn change(&mut self, index: u32, value: bool) -> Option<bool> {
+		if value {
+			self.set(index)
+		} else {
+			self.unset(index)
+		}
+	}
+
+	/// Changes the bit at the specified index to `value` without checking
+	/// that the index is in bounds, and returns original value.
+	///
+	/// # Safety
+	/// Using an index that is out of bounds may lead to
+	/// undefined behavior.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0u8);
+	/// assert_eq!(unsafe { bs.change_unchecked(3, true) }, false);
+	/// assert_eq!(bs.get(3), Some(true));
+	/// assert_eq!(unsafe { bs.change_unchecked(3, false) }, true);
+	/// assert_eq!(bs.get(3), Some(false));
+	/// ```
+	#[inline]
+	pub unsafe fn change_unchecked(&mut self, index: u32, value: bool) -> bool {
+		if value {
+			self.set_unchecked(index)
+		} else {
+			self.unset_unchecked(index)
+		}
+	}
+
+	/// Sets the bit at the specified index, and returns whether
+	/// the bit was changed, i.e., whether it was previously unset.
+	///
+	/// Returns `None` if the index is out of bounds.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0u8);
+	/// assert_eq!(bs.set_if_clear(3), Some(true));
+	/// assert_eq!(bs.set_if_clear(3), Some(false));
+	/// assert_eq!(bs.set_if_clear(8), None);
+	/// ```
+	#[inline]
+	pub fn set_if_clear(&mut self, index: u32) -> Option<bool> {
+		self.set(index).map(|old| !old)
+	}
+
+	/// Unsets the bit at the specified index, and returns whether
+	/// the bit was changed, i.e., whether it was previously set.
+	///
+	/// Returns `None` if the index is out of bounds.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0u8);
+	/// bs.set(3);
+	/// assert_eq!(bs.unset_if_set(3), Some(true));
+	/// assert_eq!(bs.unset_if_set(3), Some(false));
+	/// assert_eq!(bs.unset_if_set(8), None);
+	/// ```
+	#[inline]
+	pub fn unset_if_set(&mut self, index: u32) -> Option<bool> {
+		self.unset(index)
+	}
+
+	/// Exchanges the values of the bits at indices `i` and `j`.
+	///
+	/// Returns `None`, and leaves `self` unchanged, if either index is
+	/// out of bounds.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0b0000_0010u8);
+	/// assert_eq!(bs.swap_bits(1, 6), Some(()));
+	/// assert_eq!(bs, BitSet::from(0b0100_0000u8));
+	/// assert_eq!(bs.swap_bits(1, 8), None);
+	/// ```
+	#[inline]
+	pub fn swap_bits(&mut self, i: u32, j: u32) -> Option<()> {
+		let (a, b) = (self.get(i)?, self.get(j)?);
+		if a != b {
+			// SAFETY: Both indices are in bounds
+			unsafe {
+				self.change_unchecked(i, b);
+				self.change_unchecked(j, a);
+			}
+		}
+
+		Some(())
+	}
+
+	/// Performs the union of two `BitSet`s, modifying `self` in place.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs1 = BitSet::from(0u16);
+	/// let mut bs2 = BitSet::from(0o16);
+	///
+	/// bs1.set(3);
+	/// bs1.set(7);
+	///
+	/// bs2.set(7);
+	/// bs2.set(9);
+	///
+	/// bs1.union_with(&bs2);
+	///
+	/// assert_eq!(bs1.get(3), Some(true));
+	/// assert_eq!(bs1.get(7), Some(true));
+	/// assert_eq!(bs1.get(9), Some(true));
+	/// ```
+	#[inline]
+	pub fn union_with(&mut self, other: &Self) {
+		self.bits.union_with(&other.bits);
+	}
+
+	/// Performs the intersection of two `BitSet`s, modifying `self` in place.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs1 = BitSet::from(0u16);
+	/// let mut bs2 = BitSet::from(0u16);
+	///
+	/// bs1.set(3);
+	/// bs1.set(7);
+	///
+	/// bs2.set(7);
+	/// bs2.set(9);
+	///
+	/// bs1.intersect_with(&bs2);
+	///
+	/// assert_eq!(bs1.get(3), Some(false));
+	/// assert_eq!(bs1.get(7), Some(true));
+	/// assert_eq!(bs1.get(9), Some(false));
+	/// ```
+	#[inline]
+	pub fn intersect_with(&mut self, other: &Self) {
+		self.bits.intersect_with(&other.bits);
+	}
+
+	/// Performs the difference of two `BitSet`s, modifying `self` in place.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs1 = BitSet::from(0u16);
+	/// let mut bs2 = BitSet::from(0u16);
+	///
+	/// bs1.set(3);
+	/// bs1.set(7);
+	///
+	/// bs2.set(7);
+	/// bs2.set(9);
+	///
+	/// bs1.difference_with(&bs2);
+	///
+	/// assert_eq!(bs1.get(3), Some(true));
+	/// assert_eq!(bs1.get(7), Some(false));
+	/// assert_eq!(bs1.get(9), Some(false));
+	/// ```
+	#[inline]
+	pub fn difference_with(&mut self, other: &Self) {
+		self.bits.difference_with(&other.bits);
+	}
+
+	/// Keeps only the bits set in `self` but not in `other`, modifying `self`
+	/// in place. This is the same as [BitSet::difference_with], named after
+	/// the `andnot` instructions found in SIMD instruction sets.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs1 = BitSet::from(0b1100u8);
+	/// let mut bs2 = bs1;
+	/// let other = BitSet::from(0b1010u8);
+	///
+	/// bs1.and_not_with(&other);
+	/// bs2.difference_with(&other);
+	/// assert_eq!(bs1, bs2);
+	/// assert_eq!(bs1, BitSet::from(0b0100u8));
+	/// ```
+	#[inline]
+	pub fn and_not_with(&mut self, other: &Self) {
+		self.difference_with(other);
+	}
+
+	/// Performs the symmetric difference of two `BitSet`s, modifying `self` in place.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs1 = BitSet::from(0u16);
+	/// let mut bs2 = BitSet::from(0u16);
+	///
+	/// bs1.set(3);
+	/// bs1.set(7);
+	///
+	/// bs2.set(7);
+	/// bs2.set(9);
+	///
+	/// bs1.symmetric_difference_with(&bs2);
+	///
+	/// assert_eq!(bs1.get(3), Some(true));
+	/// assert_eq!(bs1.get(7), Some(false));
+	/// assert_eq!(bs1.get(9), Some(true));
+	/// ```
+	#[inline]
+	pub fn symmetric_difference_with(&mut self, other: &Self) {
+		self.bits.symmetric_difference_with(&other.bits);
+	}
+
+	/// XORs the bits of a raw store into `self`, modifying `self` in place.
+	///
+	/// This is the same as [BitSet::symmetric_difference_with], but without
+	/// wrapping `raw` in a `BitSet` first.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0b0110u8);
+	/// bs.xor_assign_store(&0b0011);
+	/// assert_eq!(bs, BitSet::from(0b0101u8));
+	/// ```
+	#[inline]
+	pub fn xor_assign_store(&mut self, raw: &S) {
+		self.bits.symmetric_difference_with(raw);
+	}
+
+	/// XORs the bits of a raw store into `self`, modifying `self` in place.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0x1234_5678u32);
+	/// bs.xor_with_value(0xFFFF_0000);
+	/// assert_eq!(bs, BitSet::from(0xEDCB_5678u32));
+	/// ```
+	#[inline]
+	pub fn xor_with_value(&mut self, value: S) {
+		self.bits.symmetric_difference_with(&value);
+	}
+
+	/// Sets the bit at `i + dst_offset` for every bit `i` set in `src`,
+	/// dropping bits that would land past the end of `self`. Bits that are
+	/// already set in `self` stay set.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0b0000_0001u16);
+	/// bs.blit(&BitSet::from(0b1011u8), 4);
+	/// assert_eq!(bs, BitSet::from(0b1011_0001u16));
+	/// ```
+	pub fn blit<T: BitStore>(&mut self, src: &BitSet<T>, dst_offset: u32) {
+		for i in src.ones() {
+			match i.checked_add(dst_offset) {
+				// SAFETY: `index` is in range 0..S::BITS.
+				Some(index) if index < S::BITS => unsafe { self.bits.set(index) },
+				_ => break,
+			}
+		}
+	}
+
+	/// Combines each bit of `self` with the bit at the same index in `other`
+	/// using `op`, modifying `self` in place. The first argument to `op` is
+	/// the bit from `self`.
+	///
+	/// `op` is only called once for each of the four input combinations.
+	/// Truth tables of the standard set operations, such as union and
+	/// intersection, are forwarded to the store's own word operations, and
+	/// any other table is applied a whole word at a time, so no per-bit work
+	/// is done.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0b1100u8);
+	/// bs.combine_with(&BitSet::from(0b1010), |a, b| !(a && b));
+	/// assert_eq!(bs, BitSet::from(0b1111_0111u8));
+	/// ```
+	pub fn combine_with<F: Fn(bool, bool) -> bool>(&mut self, other: &Self, op: F) {
+		let table = (
+			op(false, false),
+			op(false, true),
+			op(true, false),
+			op(true, true),
+		);
+		// The table holds `op(a, b)` for `(a, b)` = 00, 01, 10 and 11.
+		match table {
+			(false, false, false, false) => return self.bits.clear(),
+			(true, true, true, true) => return self.bits.fill(),
+			(false, false, true, true) => return,
+			(false, false, false, true) => return self.bits.intersect_with(&other.bits),
+			(false, true, true, true) => return self.bits.union_with(&other.bits),
+			(false, false, true, false) => return self.bits.difference_with(&other.bits),
+			(false, true, true, false) => return self.bits.symmetric_difference_with(&other.bits),
+			_ => {}
+		}
+
+		let mask = |bit| if bit { S::Word::FULL } else { S::Word::EMPTY };
+		let (ff, ft, tf, tt) = (mask(table.0), mask(table.1), mask(table.2), mask(table.3));
+
+		for i in 0..S::WORDS {
+			// SAFETY: `i` is in range 0..S::WORDS.
+			unsafe {
+				let (a, b) = (self.bits.word(i), other.bits.word(i));
+				let word = (!a & !b & ff) | (!a & b & ft) | (a & !b & tf) | (a & b & tt);
+				self.bits.set_word(i, word);
+			}
+		}
+	}
+
+	/// Performs the negation of all the bits, modifying `self` in place.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs1 = BitSet::from(0u16);
+	///
+	/// bs1.set(3);
+	/// bs1.set(7);
+	/// bs1.negate();
+	///
+	/// assert_eq!(bs1.get(3), Some(false));
+	/// assert_eq!(bs1.get(7), Some(false));
+	/// assert_eq!(bs1.get(9), Some(true));
+	/// ```
+	#[inline]
+	pub fn negate(&mut self) {
+		self.bits.negate();
+	}
+
+	/// Unsets every bit in place.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from([0b1011u8, 0xff]);
+	/// bs.clear();
+	/// assert!(bs.is_empty());
+	/// ```
+	#[inline]
+	pub fn clear(&mut self) {
+		self.bits.clear()
+	}
+
+	/// Sets every bit in place.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from([0b1011u8, 0]);
+	/// bs.fill();
+	/// assert!(bs.is_full());
+	/// ```
+	#[inline]
+	pub fn fill(&mut self) {
+		self.bits.fill()
+	}
+
+	/// Unsets every set bit whose index `i` fails `f(i)`. `f` is only called
+	/// for the set bits, in ascending order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0b1111_0110u8);
+	/// bs.retain(|i| i % 2 == 1);
+	/// assert_eq!(bs, BitSet::from(0b1010_0010u8));
+	/// ```
+	pub fn retain<F: FnMut(u32) -> bool>(&mut self, mut f: F) {
+		let word_bits = <S::Word as BitStore>::BITS;
+		for i in 0..S::WORDS {
+			// SAFETY: `i` is in range 0..S::WORDS.
+			let word = unsafe { self.bits.word(i) };
+			let mut kept = word;
+			for index in iter::WordOnes::new(word, i * word_bits) {
+				if !f(index) {
+					// SAFETY: `index` is a bit of word `i`.
+					unsafe { kept.unset(index - i * word_bits) };
+				}
+			}
+
+			if kept != word {
+				// SAFETY: `i` is in range 0..S::WORDS.
+				unsafe { self.bits.set_word(i, kept) };
+			}
+		}
+	}
+
+	/// Sets every bit in `range`.
+	///
+	/// # Panics
+	/// Panics if `range.end` is greater than the number of bits.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from([0u8; 2]);
+	/// bs.set_range(6..10);
+	/// assert_eq!(bs, BitSet::from([0b1100_0000u8, 0b0000_0011]));
+	/// ```
+	#[inline]
+	pub fn set_range(&mut self, range: ops::Range<u32>) {
+		Self::check_range_end(range.end);
+
+		// SAFETY: `range.end` is in bounds.
+		unsafe { self.bits.set_range(range) }
+	}
+
+	/// Unsets every bit in `range`.
+	///
+	/// # Panics
+	/// Panics if `range.end` is greater than the number of bits.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from([0xffu8; 2]);
+	/// bs.unset_range(6..10);
+	/// assert_eq!(bs, BitSet::from([0b0011_1111u8, 0b1111_1100]));
+	/// ```
+	#[inline]
+	pub fn unset_range(&mut self, range: ops::Range<u32>) {
+		Self::check_range_end(range.end);
+
+		// SAFETY: `range.end` is in bounds.
+		unsafe { self.bits.unset_range(range) }
+	}
+
+	/// Flips every bit in `range`.
+	///
+	/// # Panics
+	/// Panics if `range.end` is greater than the number of bits.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from([0b1010_1010u8, 0]);
+	/// bs.toggle_range(4..12);
+	/// assert_eq!(bs, BitSet::from([0b0101_1010u8, 0b0000_1111]));
+	/// ```
+	#[inline]
+	pub fn toggle_range(&mut self, range: ops::Range<u32>) {
+		Self::check_range_end(range.end);
+
+		// SAFETY: `range.end` is in bounds.
+		unsafe { self.bits.toggle_range(range) }
+	}
+
+	/// Unsets every bit outside of `range`, keeping only the bits inside it.
+	///
+	/// # Panics
+	/// Panics if `range.end` is greater than the number of bits.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0b1111_1111u8);
+	/// bs.retain_range(2..5);
+	/// assert_eq!(bs, BitSet::from(0b0001_1100u8));
+	/// ```
+	#[inline]
+	pub fn retain_range(&mut self, range: ops::Range<u32>) {
+		Self::check_range_end(range.end);
+
+		for i in 0..S::WORDS {
+			let mask = store::range_word_mask(&range, i);
+			// SAFETY: `i` is in range 0..S::WORDS.
+			unsafe { self.bits.set_word(i, self.bits.word(i) & mask) };
+		}
+	}
+
+	/// Unsets every bit at an index strictly greater than `index`. The bit
+	/// at `index` itself is kept.
+	///
+	/// If `index` is out of bounds there are no bits above it, and `self`
+	/// is left unchanged.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0b1111_1111u8);
+	/// bs.clear_above(4);
+	/// assert_eq!(bs, BitSet::from(0b0001_1111u8));
+	/// ```
+	#[inline]
+	pub fn clear_above(&mut self, index: u32) {
+		self.retain_range(0..index.saturating_add(1).min(S::BITS));
+	}
+
+	/// Unsets every bit at an index strictly less than `index`. The bit
+	/// at `index` itself is kept.
+	///
+	/// If `index` is out of bounds every bit is below it, and `self` is
+	/// cleared.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0b1111_1111u8);
+	/// bs.clear_below(4);
+	/// assert_eq!(bs, BitSet::from(0b1111_0000u8));
+	/// ```
+	#[inline]
+	pub fn clear_below(&mut self, index: u32) {
+		self.retain_range(index.min(S::BITS)..S::BITS);
+	}
+
+	/// Shifts all bits towards higher indices by `by` positions, modifying
+	/// `self` in place. Bits shifted past the end are discarded, and the
+	/// vacated low bits are unset.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from([0b1000_0001u8, 0]);
+	/// bs.shift_left(3);
+	/// assert_eq!(bs, BitSet::from([0b0000_1000u8, 0b0000_0100]));
+	/// ```
+	pub fn shift_left(&mut self, by: u32) {
+		let word_bits = <S::Word as BitStore>::BITS;
+		let (word_shift, bit_shift) = (by / word_bits, by % word_bits);
+		for i in (0..S::WORDS).rev() {
+			// SAFETY: all word indices are in range 0..S::WORDS, and words
+			// are only read from indices that have not been written yet.
+			unsafe {
+				let word = if i >= word_shift {
+					let src = i - word_shift;
+					let mut word = self.bits.word(src).shl_word(bit_shift);
+					if bit_shift > 0 && src > 0 {
+						word = word | self.bits.word(src - 1).shr_word(word_bits - bit_shift);
+					}
+					word
+				} else {
+					S::Word::EMPTY
+				};
+
+				self.bits.set_word(i, word);
+			}
+		}
+	}
+
+	/// Shifts all bits towards lower indices by `by` positions, modifying
+	/// `self` in place. Bits shifted past index 0 are discarded, and the
+	/// vacated high bits are unset.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from([0b1000_0001u8, 0b0000_0100]);
+	/// bs.shift_right(3);
+	/// assert_eq!(bs, BitSet::from([0b1001_0000u8, 0]));
+	/// ```
+	pub fn shift_right(&mut self, by: u32) {
+		let word_bits = <S::Word as BitStore>::BITS;
+		let (word_shift, bit_shift) = (by / word_bits, by % word_bits);
+		for i in 0..S::WORDS {
+			// SAFETY: all word indices are in range 0..S::WORDS, and words
+			// are only read from indices that have not been written yet.
+			unsafe {
+				let word = if word_shift < S::WORDS - i {
+					let src = i + word_shift;
+					let mut word = self.bits.word(src).shr_word(bit_shift);
+					if bit_shift > 0 && src + 1 < S::WORDS {
+						word = word | self.bits.word(src + 1).shl_word(word_bits - bit_shift);
+					}
+					word
+				} else {
+					S::Word::EMPTY
+				};
+
+				self.bits.set_word(i, word);
+			}
+		}
+	}
+
+	/// Shifts all bits by `by` positions, towards higher indices if `by` is
+	/// positive and towards lower indices if it is negative, setting the
+	/// vacated positions to `fill`.
+	///
+	/// If `by` is at least the number of bits in either direction, every bit
+	/// ends up as `fill`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0b1000_0110u8);
+	/// bs.shift_with_fill(-2, true);
+	/// assert_eq!(bs, BitSet::from(0b1110_0001u8));
+	/// ```
+	pub fn shift_with_fill(&mut self, by: i32, fill: bool) {
+		let distance = by.unsigned_abs();
+		let vacated = if by >= 0 {
+			self.shift_left(distance);
+			0..distance.min(S::BITS)
+		} else {
+			self.shift_right(distance);
+			S::BITS - distance.min(S::BITS)..S::BITS
+		};
+
+		if fill {
+			for i in 0..S::WORDS {
+				let mask = store::range_word_mask(&vacated, i);
+				// SAFETY: `i` is in range 0..S::WORDS.
+				unsafe { self.bits.set_word(i, self.bits.word(i) | mask) };
+			}
+		}
+	}
+
+	/// Shifts all bits towards higher indices by `count` positions, and
+	/// writes the low `count` bits of `bits` into the vacated positions.
+	///
+	/// This appends a field to the low end of the set, which makes it the
+	/// basic building block for assembling a bit stream most significant
+	/// field first.
+	///
+	/// # Panics
+	/// Panics if `count` is greater than 32.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0u16);
+	/// bs.push_low(0b101, 3);
+	/// bs.push_low(0b0011, 4);
+	/// assert_eq!(bs, BitSet::from(0b101_0011u16));
+	/// ```
+	pub fn push_low(&mut self, bits: u32, count: u32) {
+		if count > 32 {
+			panic!(
+				"count out of bounds: the max is 32 but the count is {}",
+				count
+			);
+		}
+
+		self.shift_left(count);
+		for i in 0..count.min(S::BITS) {
+			if (bits >> i) & 1 != 0 {
+				// SAFETY: `i` is in range 0..S::BITS.
+				unsafe { self.bits.set(i) };
+			}
+		}
+	}
+
+	/// Writes the low `count` bits of `value` into the low `count` bits of
+	/// the set, LSB-first. This is the inverse of [BitSet::pack_low]; bits
+	/// past the end of the set are ignored.
+	///
+	/// # Panics
+	/// Panics if `count` is greater than 32.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0b1111_1111u8);
+	/// bs.unpack_low(0b0101, 4);
+	/// assert_eq!(bs, BitSet::from(0b1111_0101u8));
+	/// ```
+	pub fn unpack_low(&mut self, value: u32, count: u32) {
+		if count > 32 {
+			panic!(
+				"count out of bounds: the max is 32 but the count is {}",
+				count
+			);
+		}
+
+		for i in 0..count.min(S::BITS) {
+			// SAFETY: `i` is in range 0..S::BITS.
+			unsafe {
+				if (value >> i) & 1 != 0 {
+					self.bits.set(i);
+				} else {
+					self.bits.unset(i);
+				}
+			}
+		}
+	}
+
+	/// Clears the lowest set bit, and returns its index.
+	///
+	/// Returns `None` if the `BitSet` is empty.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0b0010_1000u8);
+	/// assert_eq!(bs.clear_lowest_set(), Some(3));
+	/// assert_eq!(bs.clear_lowest_set(), Some(5));
+	/// assert_eq!(bs.clear_lowest_set(), None);
+	/// ```
+	#[inline]
+	pub fn clear_lowest_set(&mut self) -> Option<u32> {
+		let index = self.bits.trailing_zeros();
+		if index >= S::BITS {
+			None
+		} else {
+			// SAFETY: The index is in bounds
+			unsafe { self.bits.unset(index) };
+			Some(index)
+		}
+	}
+
+	/// Returns `true` if `self` is a subset of `other`,
+	/// i.e., every bit in `self` is set in `other`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs1 = BitSet::from(0u8);
+	/// let mut bs2 = BitSet::from(0u8);
+	///
+	/// bs1.set(7);
+	///
+	/// bs2.set(3);
+	/// bs2.set(7);
+	///
+	/// assert!(bs1.is_subset(&bs2));
+	/// ```
+	#[inline]
+	pub fn is_subset(&self, other: &Self) -> bool {
+		self.bits.is_subset_of(&other.bits)
+	}
+
+	/// Returns `true` if `self` is a superset of `other`,
+	/// i.e., every bit in `other` is set in `self`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs1 = BitSet::from(0u8);
+	/// let mut bs2 = BitSet::from(0u8);
+	///
+	/// bs1.set(3);
+	/// bs1.set(7);
+	///
+	/// bs2.set(7);
+	///
+	/// assert!(bs1.is_superset(&bs2));
+	/// ```
+	#[inline]
+	pub fn is_superset(&self, other: &Self) -> bool {
+		other.is_subset(self)
+	}
+
+	/// Returns `true` if `self` and `other` have no common bits set,
+	/// i.e., their intersection is empty.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs1 = BitSet::from(0u8);
+	/// let mut bs2 = BitSet::from(0u8);
+	///
+	/// bs1.set(3);
+	///
+	/// bs2.set(7);
+	///
+	/// assert!(bs1.is_disjoint(&bs2));
+	/// ```
+	#[inline]
+	pub fn is_disjoint(&self, other: &Self) -> bool {
+		self.bits.is_disjoint_from(&other.bits)
+	}
+}
+
+impl<S: BitStore> From<S> for BitSet<S> {
+	#[inline]
+	fn from(bits: S) -> Self {
+		Self { bits }
+	}
+}
+
+/// Generates the backing store from the unstructured bytes, so fuzz targets
+/// can take a `BitSet` as input directly.
+#[cfg(feature = "arbitrary")]
+impl<'a, S: BitStore + arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for BitSet<S> {
+	#[inline]
+	fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+		S::arbitrary(u).map(Self::from)
+	}
+
+	#[inline]
+	fn size_hint(depth: usize) -> (usize, Option<usize>) {
+		S::size_hint(depth)
+	}
+}
+
+/// Serializes the backing store as is, so a `BitSet` has the same
+/// representation as its store in every format.
+#[cfg(feature = "serde")]
+impl<S: BitStore + serde::Serialize> serde::Serialize for BitSet<S> {
+	#[inline]
+	fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+		self.bits.serialize(serializer)
+	}
+}
+
+/// Deserializes the backing store as is, accepting anything the store
+/// itself accepts.
+#[cfg(feature = "serde")]
+impl<'de, S: BitStore + serde::Deserialize<'de>> serde::Deserialize<'de> for BitSet<S> {
+	#[inline]
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		S::deserialize(deserializer).map(Self::from)
+	}
+}
+
+impl<S: BitStore> ops::Index<u32> for BitSet<S> {
+	type Output = bool;
+
+	#[inline]
+	fn index(&self, index: u32) -> &Self::Output {
+		if index >= S::BITS {
+			panic!(
+				"index out of bounds: the len is {} but the index is {}",
+				S::BITS,
+				index
+			);
+		}
+
+		// SAFETY: the index is in bounds
+		match unsafe { self.bits.get(index) } {
+			true => &true,
+			false => &false,
+		}
+	}
+}
+
+impl<S: BitStoreMut + Clone> BitSet<S> {
+	/// Performs the union of two `BitSet`s.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs1 = BitSet::from(0u16);
+	/// let mut bs2 = BitSet::from(0o16);
+	///
+	/// bs1.set(3);
+	/// bs1.set(7);
+	///
+	/// bs2.set(7);
+	/// bs2.set(9);
+	///
+	/// let bs3 = bs1.union(&bs2);
+	///
+	/// assert_eq!(bs3.get(3), Some(true));
+	/// assert_eq!(bs3.get(7), Some(true));
+	/// assert_eq!(bs3.get(9), Some(true));
+	/// ```
+	#[inline]
+	pub fn union(&self, other: &Self) -> Self {
+		let mut bits = self.bits.clone();
+		bits.union_with(&other.bits);
+		Self { bits }
+	}
+
+	/// Performs the intersection of two `BitSet`s.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs1 = BitSet::from(0u16);
+	/// let mut bs2 = BitSet::from(0u16);
+	///
+	/// bs1.set(3);
+	/// bs1.set(7);
+	///
+	/// bs2.set(7);
+	/// bs2.set(9);
+	///
+	/// let bs3 = bs1.intersection(&bs2);
+	///
+	/// assert_eq!(bs3.get(3), Some(false));
+	/// assert_eq!(bs3.get(7), Some(true));
+	/// assert_eq!(bs3.get(9), Some(false));
+	/// ```
+	#[inline]
+	pub fn intersection(&self, other: &Self) -> Self {
+		let mut bits = self.bits.clone();
+		bits.intersect_with(&other.bits);
+		Self { bits }
+	}
+
+	/// Performs the difference of two `BitSet`s.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs1 = BitSet::from(0u16);
+	/// let mut bs2 = BitSet::from(0u16);
+	///
+	/// bs1.set(3);
+	/// bs1.set(7);
+	///
+	/// bs2.set(7);
+	/// bs2.set(9);
+	///
+	/// let bs3 = bs1.difference(&bs2);
+	///
+	/// assert_eq!(bs3.get(3), Some(true));
+	/// assert_eq!(bs3.get(7), Some(false));
+	/// assert_eq!(bs3.get(9), Some(false));
+	/// ```
+	#[inline]
+	pub fn difference(&self, other: &Self) -> Self {
+		let mut bits = self.bits.clone();
+		bits.difference_with(&other.bits);
+		Self { bits }
+	}
+
+	/// Returns the bits set in `self` but not in `other`. This is the same as
+	/// [BitSet::difference], named after the `andnot` instructions found in
+	/// SIMD instruction sets.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b1100u8);
+	/// let other = BitSet::from(0b1010u8);
+	///
+	/// assert_eq!(bs.and_not(&other), bs.difference(&other));
+	/// assert_eq!(bs.and_not(&other), BitSet::from(0b0100u8));
+	/// ```
+	#[inline]
+	pub fn and_not(&self, other: &Self) -> Self {
+		self.difference(other)
+	}
+
+	/// Performs the symmetric difference of two `BitSet`s.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs1 = BitSet::from(0u16);
+	/// let mut bs2 = BitSet::from(0u16);
+	///
+	/// bs1.set(3);
+	/// bs1.set(7);
+	///
+	/// bs2.set(7);
+	/// bs2.set(9);
+	///
+	/// let bs3 = bs1.symmetric_difference(&bs2);
+	///
+	/// assert_eq!(bs3.get(3), Some(true));
+	/// assert_eq!(bs3.get(7), Some(false));
+	/// assert_eq!(bs3.get(9), Some(true));
+	/// ```
+	#[inline]
+	pub fn symmetric_difference(&self, other: &Self) -> Self {
+		let mut bits = self.bits.clone();
+		bits.symmetric_difference_with(&other.bits);
+		Self { bits }
+	}
+
+	/// Computes `a ^ b ^ c`, i.e., the bits set in an odd number of the
+	/// three sets.
+	///
+	/// Unlike chaining [BitSet::symmetric_difference], this makes a single
+	/// pass over the words and only creates one new `BitSet`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let a = BitSet::from(0b1100u8);
+	/// let b = BitSet::from(0b1010u8);
+	/// let c = BitSet::from(0b1001u8);
+	/// assert_eq!(BitSet::xor3(&a, &b, &c), BitSet::from(0b1111u8));
+	/// ```
+	pub fn xor3(a: &Self, b: &Self, c: &Self) -> Self {
+		let mut bits = a.bits.clone();
+		for i in 0..S::WORDS {
+			// SAFETY: `i` is in range 0..S::WORDS.
+			unsafe {
+				let word = bits.word(i) ^ b.bits.word(i) ^ c.bits.word(i);
+				bits.set_word(i, word);
+			}
+		}
+
+		Self { bits }
+	}
+
+	/// Performs the negation of all the bits.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs1 = BitSet::from(0u16);
+	///
+	/// bs1.set(3);
+	/// bs1.set(7);
+	/// let bs2 = bs1.negation();
+	///
+	/// assert_eq!(bs2.get(3), Some(false));
+	/// assert_eq!(bs2.get(7), Some(false));
+	/// assert_eq!(bs2.get(9), Some(true));
+	#[inline]
+	pub fn negation(&self) -> Self {
+		let mut bits = self.bits.clone();
+		bits.negate();
+		Self { bits }
+	}
+
+	/// Returns a new `BitSet` where every backing word of `self` has been
+	/// transformed by `f`, in order from the lowest word to the highest.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from([0b1111_0000u8, 0b1010_1010]);
+	/// let bs = bs.map_words(|w| w & 0b0011_1100);
+	/// assert_eq!(bs, BitSet::from([0b0011_0000u8, 0b0010_1000]));
+	/// ```
+	#[inline]
+	pub fn map_words<F: FnMut(S::Word) -> S::Word>(&self, mut f: F) -> Self {
+		let mut bits = self.bits.clone();
+		for i in 0..S::WORDS {
+			// SAFETY: `i` is in range 0..S::WORDS.
+			unsafe { bits.set_word(i, f(bits.word(i))) };
+		}
+
+		Self { bits }
+	}
+
+	/// Returns a new `BitSet` where bit `i` is set if any bit within
+	/// `i - radius..=i + radius` is set in `self`.
+	///
+	/// Bits outside of the `BitSet` are treated as unset.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b0001_0001u8);
+	/// assert_eq!(bs.dilate(1), BitSet::from(0b0011_1011u8));
+	/// ```
+	pub fn dilate(&self, radius: u32) -> Self {
+		let mut result = self.clone();
+		let mut up = self.clone();
+		let mut down = self.clone();
+		for _ in 0..radius.min(S::BITS) {
+			up.shift_left(1);
+			down.shift_right(1);
+			result.union_with(&up);
+			result.union_with(&down);
+		}
+
+		result
+	}
+
+	/// Returns a new `BitSet` where bit `i` is set only if all bits within
+	/// `i - radius..=i + radius` are set in `self`.
+	///
+	/// This is the dual of [BitSet::dilate], so bits outside of the `BitSet`
+	/// are treated as set: a run of set bits touching either end is only
+	/// eroded from its inner side.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b1101_1100u8);
+	/// assert_eq!(bs.erode(1), BitSet::from(0b1000_1000u8));
+	/// ```
+	pub fn erode(&self, radius: u32) -> Self {
+		self.negation().dilate(radius).negation()
+	}
+
+	/// Returns an iterator over `(offset, overlap)` pairs for every offset in
+	/// `-(len - 1)..len`, where `overlap` is the number of bits set in both
+	/// `self` and `other` shifted towards higher indices by `offset`.
+	///
+	/// The offset with the highest overlap is the best alignment of the two
+	/// sets.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let a = BitSet::from(0b0110_0000u8);
+	/// let b = BitSet::from(0b0000_0011u8);
+	/// let best = a.correlate(&b).max_by_key(|&(_, overlap)| overlap);
+	/// assert_eq!(best, Some((5, 2)));
+	/// ```
+	pub fn correlate<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = (i32, u32)> + 'a {
+		let bits = S::BITS as i32;
+		(1 - bits..bits).map(move |offset| {
+			let mut shifted = other.clone();
+			if offset >= 0 {
+				shifted.shift_left(offset as u32);
+			} else {
+				shifted.shift_right(offset.unsigned_abs());
+			}
+
+			shifted.intersect_with(self);
+			(offset, shifted.bits.count_ones())
+		})
+	}
+
+	/// Shifts all bits towards higher indices by `by` positions like
+	/// [BitSet::shift_left], and returns the bits that were shifted past the
+	/// end.
+	///
+	/// The returned bits are positioned as if they had continued into the
+	/// low end of a following `BitSet`, so that OR-ing them into the next set
+	/// after shifting it as well treats a sequence of sets as one long shift
+	/// register.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0b1100_0001u8);
+	/// let overflow = bs.shift_left_overflowing(3);
+	/// assert_eq!(bs, BitSet::from(0b0000_1000u8));
+	/// assert_eq!(overflow, BitSet::from(0b0000_0110u8));
+	/// ```
+	pub fn shift_left_overflowing(&mut self, by: u32) -> Self {
+		let mut overflow = self.clone();
+		if by <= S::BITS {
+			overflow.shift_right(S::BITS - by);
+		} else {
+			overflow.shift_left(by - S::BITS);
+		}
+
+		self.shift_left(by);
+		overflow
+	}
+
+	/// Shifts all bits towards lower indices by `by` positions like
+	/// [BitSet::shift_right], and returns the bits that were shifted past
+	/// index 0.
+	///
+	/// The returned bits are positioned as if they had continued into the
+	/// high end of a preceding `BitSet`, mirroring
+	/// [BitSet::shift_left_overflowing].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0b1000_0011u8);
+	/// let overflow = bs.shift_right_overflowing(3);
+	/// assert_eq!(bs, BitSet::from(0b0001_0000u8));
+	/// assert_eq!(overflow, BitSet::from(0b0110_0000u8));
+	/// ```
+	pub fn shift_right_overflowing(&mut self, by: u32) -> Self {
+		let mut overflow = self.clone();
+		if by <= S::BITS {
+			overflow.shift_left(S::BITS - by);
+		} else {
+			overflow.shift_right(by - S::BITS);
+		}
+
+		self.shift_right(by);
+		overflow
+	}
+
+	/// Returns a new `BitSet` containing the bits of `range`, moved down so
+	/// that `range.start` ends up at index 0. All other bits are unset.
+	///
+	/// # Panics
+	/// Panics if `range.end` is greater than the number of bits.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b0110_1100u8);
+	/// assert_eq!(bs.slice(2..6), BitSet::from(0b0000_1011u8));
+	/// ```
+	#[inline]
+	pub fn slice(&self, range: ops::Range<u32>) -> Self {
+		let mut bits = self.clone();
+		let start = range.start;
+		bits.retain_range(range);
+		bits.shift_right(start);
+		bits
+	}
+}
+
+impl<S: BitStoreMut + BitStoreConst> BitSet<S> {
+	/// Creates a `BitSet` where bit `i` is set if `f(i)` returns `true`,
+	/// calling `f` for every index in ascending order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::<u32>::from_fn(|i| i % 3 == 0);
+	/// assert!(bs.ones().eq((0..32).step_by(3)));
+	/// ```
+	pub fn from_fn<F: FnMut(u32) -> bool>(mut f: F) -> Self {
+		let mut bits = S::EMPTY;
+		for i in 0..S::BITS {
+			if f(i) {
+				// SAFETY: `i` is in range 0..S::BITS.
+				unsafe { bits.set(i) };
+			}
+		}
+
+		Self { bits }
+	}
+
+	/// Creates a `BitSet` from indices without checking that they are in
+	/// bounds.
+	///
+	/// # Safety
+	/// All indices must be in range 0..[BitSet::len]. Unlike
+	/// [BitSet::from_sorted], the order of the indices is not checked either,
+	/// so duplicates are simply set twice.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// // SAFETY: all indices are less than 16.
+	/// let bs = unsafe { BitSet::<u16>::from_sorted_unchecked([1, 4, 15]) };
+	/// assert_eq!(bs, BitSet::from(0b1000_0000_0001_0010u16));
+	/// ```
+	#[inline]
+	pub unsafe fn from_sorted_unchecked<I: IntoIterator<Item = u32>>(iter: I) -> Self {
+		let mut bits = S::EMPTY;
+		for i in iter {
+			bits.set(i);
+		}
+
+		Self { bits }
+	}
+
+	/// Creates a `BitSet` from strictly increasing indices.
+	///
+	/// Returns `None` if an index is out of bounds, or if an index is not
+	/// greater than the one before it, i.e., the indices are unsorted or
+	/// contain duplicates.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// assert_eq!(BitSet::<u8>::from_sorted([1, 4, 7]), Some(BitSet::from(0b1001_0010u8)));
+	/// assert_eq!(BitSet::<u8>::from_sorted([1, 4, 4]), None);
+	/// assert_eq!(BitSet::<u8>::from_sorted([4, 1]), None);
+	/// assert_eq!(BitSet::<u8>::from_sorted([8]), None);
+	/// ```
+	pub fn from_sorted<I: IntoIterator<Item = u32>>(iter: I) -> Option<Self> {
+		let mut bits = S::EMPTY;
+		let mut next = 0;
+		for i in iter {
+			if i < next || i >= S::BITS {
+				return None;
+			}
+
+			// SAFETY: `i` is in range.
+			unsafe { bits.set(i) };
+			next = i + 1;
+		}
+
+		Some(Self { bits })
+	}
+
+	/// Creates a `BitSet` with exactly `k` distinct bits chosen uniformly at
+	/// random, or a full `BitSet` if `k` is at least [BitSet::len].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// use rand::{rngs::SmallRng, SeedableRng};
+	///
+	/// let mut rng = SmallRng::seed_from_u64(7);
+	/// let bs = BitSet::<[u8; 4]>::random_k(&mut rng, 5);
+	/// assert_eq!(bs.iter().filter(|&b| b).count(), 5);
+	/// ```
+	#[cfg(feature = "rand")]
+	pub fn random_k<R: rand::Rng + ?Sized>(rng: &mut R, k: u32) -> Self {
+		let mut bits = S::EMPTY;
+		// Floyd's algorithm: each step picks one new index, falling back to
+		// `j` itself if the pick was already chosen, which keeps the
+		// selection uniform.
+		for j in S::BITS - k.min(S::BITS)..S::BITS {
+			let t = rng.gen_range(0..=j);
+			// SAFETY: both `t` and `j` are in range 0..S::BITS.
+			unsafe {
+				if bits.get(t) {
+					bits.set(j);
+				} else {
+					bits.set(t);
+				}
+			}
+		}
+
+		Self { bits }
+	}
+
+	/// Returns a `BitSet` containing only the lowest set bit of `self`.
+	///
+	/// Returns an empty `BitSet` if `self` is empty.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b0010_1000u8);
+	/// assert_eq!(bs.isolate_lowest_set(), BitSet::from(0b0000_1000u8));
+	/// ```
+	#[inline]
+	pub fn isolate_lowest_set(&self) -> Self {
+		let mut bits = S::EMPTY;
+		let index = self.bits.trailing_zeros();
+		if index < S::BITS {
+			// SAFETY: The index is in bounds
+			unsafe { bits.set(index) };
+		}
+
+		Self { bits }
+	}
+
+	/// Gathers the bits of `self` at the positions set in `mask`, and packs
+	/// them into the low bits of the result, preserving their order.
+	///
+	/// This is the parallel bit extract operation (`PEXT`).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0x1234_5678u32);
+	/// let mask = BitSet::from(0xFF00_FFF0u32);
+	/// assert_eq!(bs.gather(&mask), BitSet::from(0x0001_2567u32));
+	/// ```
+	pub fn gather(&self, mask: &Self) -> Self {
+		let mut bits = S::EMPTY;
+		for (k, index) in (0..).zip(mask.ones()) {
+			// SAFETY: `index` is a set bit of `mask`, and `k <= index`.
+			unsafe {
+				if self.bits.get(index) {
+					bits.set(k);
+				}
+			}
+		}
+
+		Self { bits }
+	}
+
+	/// Scatters the low bits of `self` to the positions set in `mask`,
+	/// preserving their order.
+	///
+	/// This is the parallel bit deposit operation (`PDEP`), and the inverse
+	/// of [BitSet::gather] for the bits selected by `mask`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0x0001_2567u32);
+	/// let mask = BitSet::from(0xFF00_FFF0u32);
+	/// assert_eq!(bs.scatter(&mask), BitSet::from(0x1200_5670u32));
+	/// ```
+	pub fn scatter(&self, mask: &Self) -> Self {
+		let mut bits = S::EMPTY;
+		for (k, index) in (0..).zip(mask.ones()) {
+			// SAFETY: `index` is a set bit of `mask`, and `k <= index`.
+			unsafe {
+				if self.bits.get(k) {
+					bits.set(index);
+				}
+			}
+		}
+
+		Self { bits }
+	}
+
+	/// Returns a new `BitSet` where bit `i` of `self` is moved to bit
+	/// `i * stride`. Bits that would move past the end are discarded.
+	///
+	/// With a `stride` of 2 this spreads the bits out with a zero between
+	/// each of them, which is the first step of computing a Morton code.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from(0b0000_1011u16);
+	/// assert_eq!(bs.spread(2), BitSet::from(0b0100_0101u16));
+	/// ```
+	pub fn spread(&self, stride: u32) -> Self {
+		let mut bits = S::EMPTY;
+		for i in self.ones() {
+			match i.checked_mul(stride) {
+				// SAFETY: `index` is in bounds.
+				Some(index) if index < S::BITS => unsafe { bits.set(index) },
+				_ => break,
+			}
+		}
+
+		Self { bits }
+	}
+}
+
+impl<T: BitStore, const N: usize> BitSet<[T; N]> {
+	/// Returns the number of set bits before each element of the backing
+	/// array, so `prefix[i]` is the number of bits set in elements `0..i`.
+	///
+	/// The table can be passed to [BitSet::rank_with] to count the bits below
+	/// an index without scanning all the elements before it.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from([0b0011u8, 0b0111, 0b0001]);
+	/// assert_eq!(bs.prefix_popcounts(), [0, 2, 5]);
+	/// ```
+	pub fn prefix_popcounts(&self) -> [u32; N] {
+		let mut total = 0;
+		self.bits.each_ref().map(|element| {
+			let before = total;
+			total += element.count_ones();
+			before
+		})
+	}
+
+	/// Returns the number of set bits at indices below `index`, using a
+	/// table from [BitSet::prefix_popcounts] so that only the element
+	/// containing `index` is scanned.
+	///
+	/// If `index` is out of bounds, all set bits are counted. The result is
+	/// meaningless if `prefix` was computed for different bits.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from([0b0011u8, 0b0111, 0b0001]);
+	/// let prefix = bs.prefix_popcounts();
+	/// assert_eq!(bs.rank_with(&prefix, 10), 4);
+	/// assert_eq!(bs.rank_with(&prefix, 24), 6);
+	/// ```
+	pub fn rank_with(&self, prefix: &[u32; N], index: u32) -> u32 {
+		if index >= <[T; N]>::BITS {
+			return prefix
+				.last()
+				.zip(self.bits.last())
+				.map_or(0, |(before, last)| before + last.count_ones());
+		}
+
+		let (element, offset) = (index / T::BITS, index % T::BITS);
+		// SAFETY: `offset` is in range 0..T::BITS.
+		prefix[element as usize] + unsafe { self.bits[element as usize].rank(offset) }
+	}
+}
+
+impl<T: BitStore + Clone, const N: usize> BitSet<[T; N]> {
+	/// Returns an iterator over the elements of the backing array, each
+	/// wrapped as its own `BitSet`, from the lowest to the highest.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from([0b0001u8, 0b0010, 0b0100]);
+	/// let sets = bs.word_sets().collect::<Vec<_>>();
+	/// assert_eq!(sets.len(), 3);
+	/// assert_eq!(sets[1], BitSet::from(0b0010u8));
+	/// ```
+	#[inline]
+	pub fn word_sets(&self) -> impl ExactSizeIterator<Item = BitSet<T>> + '_ {
+		self.bits.iter().cloned().map(BitSet::from)
+	}
+}
+
+impl<const N: usize> BitSet<[u8; N]> {
+	/// Creates a `BitSet` from bytes in little-endian order, where bit `i`
+	/// of the set is bit `i % 8` of byte `i / 8`, so bit 0 is the least
+	/// significant bit of the first byte.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let bs = BitSet::from_bytes([0b0000_0001, 0b0000_0010]);
+	/// assert!(bs.ones().eq([0, 9]));
+	/// ```
+	#[inline]
+	pub const fn from_bytes(bytes: [u8; N]) -> Self {
+		Self { bits: bytes }
+	}
+
+	/// Returns the bits as bytes in little-endian order, using the same
+	/// layout as [BitSet::from_bytes].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from([0u8; 2]);
+	/// bs.set(0);
+	/// bs.set(9);
+	/// assert_eq!(bs.to_bytes(), [0b0000_0001, 0b0000_0010]);
+	/// ```
+	#[inline]
+	pub const fn to_bytes(&self) -> [u8; N] {
+		self.bits
+	}
+}
+
+impl<S: BitStore> BitSet<MsbFirst<S>> {
+	/// Reinterprets the `BitSet` so that index 0 is the lowest bit of the
+	/// wrapped store again, undoing [BitSet::msb_first].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use bitarr::BitSet;
+	/// let mut bs = BitSet::from(0u8).msb_first();
+	/// bs.set(0);
+	/// assert_eq!(bs.lsb_first().get(7), Some(true));
+	/// ```
+	#[inline]
+	pub fn lsb_first(self) -> BitSet<S> {
+		BitSet::from(self.bits.0)
+	}
+}
+
+impl<S: BitStoreMut + Clone> ops::Neg for BitSet<S> {
+	type Output = Self;
+
+	#[inline]
+	fn neg(self) -> Self::Output {
+		self.negation()
+	}
+}
+
+impl<S: BitStoreMut + Clone> ops::BitAnd for BitSet<S> {
+	type Output = Self;
+
+	#[inline]
+	fn bitand(self, rhs: Self) -> Self::Output {
+		self.intersection(&rhs)
+	}
+}
+
+impl<S: BitStoreMut> ops::BitAnd<&BitSet<S>> for BitSet<S> {
+	type Output = Self;
+
+	#[inline]
+	fn bitand(mut self, rhs: &Self) -> Self::Output {
+		self.intersect_with(rhs);
+		self
+	}
+}
+
+impl<S: BitStoreMut> ops::BitAndAssign for BitSet<S> {
+	#[inline]
+	fn bitand_assign(&mut self, rhs: Self) {
+		self.intersect_with(&rhs);
+	}
+}
+
+impl<S: BitStoreMut + Clone> ops::BitOr for BitSet<S> {
+	type Output = Self;
+
+	#[inline]
+	fn bitor(self, rhs: Self) -> Self::Output {
+		self.union(&rhs)
+	}
+}
+
+impl<S: BitStoreMut> ops::BitOr<&BitSet<S>> for BitSet<S> {
+	type Output = Self;
+
+	#[inline]
+	fn bitor(mut self, rhs: &Self) -> Self::Output {
+		self.union_with(rhs);
+		self
+	}
+}
+
+impl<S: BitStoreMut> ops::BitOrAssign for BitSet<S> {
+	#[inline]
+	fn bitor_assign(&mut self, rhs: Self) {
+		self.union_with(&rhs);
+	}
+}
+
+impl<S: BitStoreMut + Clone> ops::BitXor for BitSet<S> {
+	type Output = Self;
+
+	#[inline]
+	fn bitxor(self, rhs: Self) -> Self::Output {
+		self.symmetric_difference(&rhs)
+	}
+}
+
+impl<S: BitStoreMut> ops::BitXor<&BitSet<S>> for BitSet<S> {
+	type Output = Self;
+
+	#[inline]
+	fn bitxor(mut self, rhs: &Self) -> Self::Output {
+		self.symmetric_difference_with(rhs);
+		self
+	}
+}
+
+impl<S: BitStoreMut> ops::BitXorAssign for BitSet<S> {
+	#[inline]
+	fn bitxor_assign(&mut self, rhs: Self) {
+		self.symmetric_difference_with(&rhs);
+	}
+}
+
+impl<S: BitStoreMut + Clone> ops::Sub for BitSet<S> {
+	type Output = Self;
+
+	#[inline]
+	fn sub(self, rhs: Self) -> Self::Output {
+		self.difference(&rhs)
+	}
+}
+
+impl<S: BitStoreMut> ops::Sub<&BitSet<S>> for BitSet<S> {
+	type Output = Self;
+
+	#[inline]
+	fn sub(mut self, rhs: &Self) -> Self::Output {
+		self.difference_with(rhs);
+		self
+	}
+}
+
+impl<S: BitStoreMut> ops::SubAssign for BitSet<S> {
+	#[inline]
+	fn sub_assign(&mut self, rhs: Self) {
+		self.difference_with(&rhs);
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexRangeError<T: fmt::Debug + fmt::Display> {
+	index: T,
+	arr_len: u32,
+}
+
+impl<T: fmt::Debug + fmt::Display> fmt::Display for IndexRangeError<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"index {} out of range for array of length {}",
+			self.index, self.arr_len
+		)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug + fmt::Display> std::error::Error for IndexRangeError<T> {}
+
+impl<T: fmt::Debug + fmt::Display> IndexRangeError<T> {
+	#[cold]
+	fn new_err<R>(index: T, arr_len: u32) -> Result<R, Self> {
+		Err(Self { index, arr_len })
+	}
+}
+
+/// Parses the output of the [fmt::Binary] implementation back into a
+/// `BitSet`.
+///
+/// The string is an optional `0b` prefix followed by exactly one `0` or `1`
+/// digit per bit, highest index first. Underscores are ignored anywhere after
+/// the prefix.
+///
+/// # Examples
+///
+/// ```
+/// # use bitarr::BitSet;
+/// let bs = "0b_0001_0000_0000_0100".parse::<BitSet<[u8; 2]>>().unwrap();
+/// assert!(bs.ones().eq([2, 12]));
+/// assert_eq!(format!("{:b}", bs).parse(), Ok(bs));
+/// assert!("0b101".parse::<BitSet<u8>>().is_err());
+/// ```
+impl<S: BitStoreConst + BitStoreMut> core::str::FromStr for BitSet<S> {
+	type Err = ParseBitSetError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let digits = s.strip_prefix("0b").unwrap_or(s);
+		let mut bits = S::EMPTY;
+		let mut count = 0usize;
+		for c in digits.chars() {
+			let bit = match c {
+				'_' => continue,
+				'0' => false,
+				'1' => true,
+				c => return Err(ParseBitSetError::InvalidDigit(c)),
+			};
+
+			if bit && count < S::BITS as usize {
+				// SAFETY: `count` is in range 0..S::BITS.
+				unsafe { bits.set(S::BITS - 1 - count as u32) };
+			}
+			count += 1;
+		}
+
+		if count != S::BITS as usize {
+			return Err(ParseBitSetError::WrongLength {
+				expected: S::BITS,
+				found: count,
+			});
+		}
+
+		Ok(Self { bits })
+	}
+}
+
+/// An error returned when parsing a `BitSet` from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBitSetError {
+	/// The string contains a character that is neither a binary digit nor
+	/// an underscore.
+	InvalidDigit(char),
+	/// The number of digits doesn't match the number of bits.
+	WrongLength { expected: u32, found: usize },
+}
+
+impl fmt::Display for ParseBitSetError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::InvalidDigit(c) => write!(f, "invalid binary digit {:?}", c),
+			Self::WrongLength { expected, found } => {
+				write!(f, "expected {} binary digits but found {}", expected, found)
+			}
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseBitSetError {}
+
+/// Creates a `BitSet` with one `bool` per bit, where bit `i` is set if
+/// `bools[i]` is `true`. The slice must have exactly one item per bit.
+///
+/// # Examples
+///
+/// ```
+/// # use bitarr::BitSet;
+/// let bools = [true, false, true, false, false, false, false, false];
+/// let bs = BitSet::<u8>::try_from(&bools[..]).unwrap();
+/// assert_eq!(bs, BitSet::from(0b101u8));
+///
+/// assert!(BitSet::<u8>::try_from(&bools[..4]).is_err());
+/// ```
+impl<S: BitStoreConst + BitStoreMut> TryFrom<&[bool]> for BitSet<S> {
+	type Error = SliceLengthError;
+
+	fn try_from(bools: &[bool]) -> Result<Self, Self::Error> {
+		if bools.len() != S::BITS as usize {
+			return Err(SliceLengthError {
+				expected: S::BITS,
+				found: bools.len(),
+			});
+		}
+
+		Ok(bools.iter().copied().collect())
+	}
+}
+
+/// An error returned when converting a slice into a `BitSet` fails because
+/// the slice doesn't have exactly one item per bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SliceLengthError {
+	expected: u32,
+	found: usize,
+}
+
+impl fmt::Display for SliceLengthError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"expected a slice of length {} but found {}",
+			self.expected, self.found
+		)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SliceLengthError {}
+
+macro_rules! impl_from_iter {
+	($t:ty) => {
+		impl<S: BitStoreMut + BitStoreConst> TryFromIterator<$t> for BitSet<S> {
+			type Error = IndexRangeError<$t>;
+
+			fn try_from_iter<I: IntoIterator<Item = $t>>(iter: I) -> Result<Self, Self::Error> {
+				let mut bits = S::EMPTY;
+				for i in iter {
+					match u32::try_from(i) {
+						Ok(i) if i < S::BITS => {
+							// SAFETY: `i` is in range.
+							unsafe { bits.set(i) };
+						}
+						_ => return IndexRangeError::new_err(i, S::BITS),
+					}
+				}
+
+				Ok(Self { bits })
+			}
+		}
+
+		/// Collects indices into a `BitSet` with exactly those bits set.
+		/// Duplicate indices are allowed.
+		///
+		/// # Panics
+		/// Panics if an index is out of bounds. Use
+		/// [TryCollectExt::try_collect] to get an [IndexRangeError]
+		/// instead.
+		impl<S: BitStoreMut + BitStoreConst> FromIterator<$t> for BitSet<S> {
+			fn from_iter<I: IntoIterator<Item = $t>>(iter: I) -> Self {
+				match Self::try_from_iter(iter) {
+					Ok(v) => v,
+					Err(e) => panic!("{}", e),
+				}
+			}
+		}
+
+		/// Sets the bits at the yielded indices, in any order.
+		///
+		/// # Panics
+		/// Panics if an index is out of bounds, with the same message as
+		/// collecting the indices. Bits set before the bad index stay set.
+		impl<S: BitStoreMut> Extend<$t> for BitSet<S> {
+			fn extend<I: IntoIterator<Item = $t>>(&mut self, iter: I) {
+				for i in iter {
+					match u32::try_from(i) {
+						Ok(i) if i < S::BITS => {
+							// SAFETY: `i` is in range.
+							unsafe { self.bits.set(i) };
+						}
+						_ => panic!(
+							"{}",
+							IndexRangeError {
+								index: i,
+								arr_len: S::BITS
+							}
+						),
+					}
+				}
+			}
+		}
+	};
+}
+
+impl_from_iter!(u64);
+impl_from_iter!(u32);
+impl_from_iter!(u16);
+impl_from_iter!(u8);
+impl_from_iter!(usize);
+
+/// Collects one `bool` per bit, from index 0 up, into a `BitSet`. This is the
+/// inverse of iterating over a `BitSet`.
+///
+/// The iterator stops being read once every bit has been given a value, and
+/// any bits it doesn't reach are left unset.
+///
+/// # Examples
+///
+/// ```
+/// # use bitarr::BitSet;
+/// let bs = [true, false, true].into_iter().collect::<BitSet<u8>>();
+/// assert_eq!(bs, BitSet::from(0b101u8));
+/// ```
+impl<S: BitStoreMut + BitStoreConst> FromIterator<bool> for BitSet<S> {
+	fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+		let mut bs = Self::empty();
+		bs.extend(iter);
+		bs
+	}
+}
+
+/// Sets bit `i` when the `i`th item is `true`, counting from index 0. Bits
+/// whose item is `false` are left as they are.
+///
+/// The iterator stops being read once every bit has been given a value.
+impl<S: BitStoreMut> Extend<bool> for BitSet<S> {
+	fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+		for (i, b) in (0..S::BITS).zip(iter) {
+			if b {
+				// SAFETY: `i` is in range 0..S::BITS.
+				unsafe { self.bits.set(i) };
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::{format, vec, vec::Vec};
+
+	#[test]
+	fn union() {
+		let mut bs1 = BitSet::from(0u16);
+		let mut bs2 = BitSet::from(0u16);
+
+		bs1.set(3);
+		bs1.set(7);
+
+		bs2.set(7);
+		bs2.set(9);
+
+		bs1.union_with(&bs2);
+
+		assert!(bs1[3]);
+		assert!(bs1[7]);
+		assert!(bs1[9]);
+	}
+
+	#[test]
+	fn intersection() {
 		let mut bs1 = BitSet::from(0u16);
 		let mut bs2 = BitSet::from(0u16);
 
-		bs1.set(3);
-		bs1.set(7);
+		bs1.set(3);
+		bs1.set(7);
+
+		bs2.set(7);
+		bs2.set(9);
+
+		bs1.intersect_with(&bs2);
+
+		assert!(!bs1[3]);
+		assert!(bs1[7]);
+		assert!(!bs1[9]);
+	}
+
+	#[test]
+	fn nested_array_store() {
+		let mut bs = BitSet::<[[u8; 2]; 3]>::new();
+		assert_eq!(bs.len(), 48);
+		assert_eq!(BitStore::trailing_zeros(&bs.bits), 48);
+		assert_eq!(BitStore::leading_zeros(&bs.bits), 48);
+
+		assert_eq!(bs.set(3), Some(false));
+		assert_eq!(bs.set(15), Some(false));
+		assert_eq!(bs.set(16), Some(false));
+		assert_eq!(bs.set(40), Some(false));
+		assert_eq!(bs.set(48), None);
+		assert_eq!(
+			bs.bits,
+			[
+				[0b0000_1000, 0b1000_0000],
+				[0b0000_0001, 0],
+				[0, 0b0000_0001]
+			]
+		);
+
+		assert_eq!(BitStore::count_ones(&bs.bits), 4);
+		assert_eq!(BitStore::trailing_zeros(&bs.bits), 3);
+		assert_eq!(BitStore::leading_zeros(&bs.bits), 7);
+		assert_eq!(bs.ones().collect::<Vec<_>>(), [3, 15, 16, 40]);
+
+		let mut other = BitSet::<[[u8; 2]; 3]>::new();
+		other.set(15);
+		other.set(47);
+
+		assert_eq!((bs | other).ones().collect::<Vec<_>>(), [3, 15, 16, 40, 47]);
+		assert_eq!((bs & other).ones().collect::<Vec<_>>(), [15]);
+		assert_eq!((bs - other).ones().collect::<Vec<_>>(), [3, 16, 40]);
+		assert_eq!((bs ^ other).ones().collect::<Vec<_>>(), [3, 16, 40, 47]);
+		assert_eq!((-bs).ones().count(), 44);
+	}
+
+	#[test]
+	fn set_if_clear() {
+		let mut bs = BitSet::from(0u16);
+
+		assert_eq!(bs.set_if_clear(5), Some(true));
+		assert!(bs[5]);
+
+		assert_eq!(bs.set_if_clear(5), Some(false));
+		assert!(bs[5]);
+
+		assert_eq!(bs.set_if_clear(16), None);
+		assert_eq!(bs, BitSet::from(0b0010_0000u16));
+	}
+
+	#[test]
+	fn unset_if_set() {
+		let mut bs = BitSet::from(0b0010_0000u16);
+
+		assert_eq!(bs.unset_if_set(5), Some(true));
+		assert!(!bs[5]);
+
+		assert_eq!(bs.unset_if_set(5), Some(false));
+		assert!(!bs[5]);
+
+		assert_eq!(bs.unset_if_set(16), None);
+		assert!(bs.is_empty());
+	}
+
+	const _: () = BitSet::<u32>::assert_min_bits::<32>();
+	const _: () = BitSet::<[u64; 2]>::assert_min_bits::<65>();
+
+	#[test]
+	fn assert_min_bits() {
+		BitSet::<u8>::assert_min_bits::<0>();
+		BitSet::<u8>::assert_min_bits::<8>();
+		BitSet::<[[u8; 2]; 3]>::assert_min_bits::<48>();
+	}
+
+	#[test]
+	fn ops_with_borrowed_rhs() {
+		let mut a = BitSet::from(alloc::boxed::Box::new(0u16));
+		let mut b = BitSet::from(alloc::boxed::Box::new(0u16));
+
+		a.set(3);
+		a.set(7);
+
+		b.set(7);
+		b.set(9);
+
+		let union = a.clone() | &b;
+		assert_eq!(union.ones().collect::<Vec<_>>(), [3, 7, 9]);
+
+		let intersection = a.clone() & &b;
+		assert_eq!(intersection.ones().collect::<Vec<_>>(), [7]);
+
+		let symmetric_difference = a.clone() ^ &b;
+		assert_eq!(symmetric_difference.ones().collect::<Vec<_>>(), [3, 9]);
+
+		let difference = a - &b;
+		assert_eq!(difference.ones().collect::<Vec<_>>(), [3]);
+
+		assert_eq!(b.ones().collect::<Vec<_>>(), [7, 9]);
+	}
+
+	#[test]
+	fn clear_lowest_set() {
+		let mut bs = BitSet::<[u8; 4]>::empty();
+		assert_eq!(bs.clear_lowest_set(), None);
+
+		bs.set(12);
+		bs.set(13);
+		bs.set(30);
+
+		assert_eq!(bs.clear_lowest_set(), Some(12));
+		assert_eq!(bs.clear_lowest_set(), Some(13));
+		assert_eq!(bs.clear_lowest_set(), Some(30));
+		assert_eq!(bs.clear_lowest_set(), None);
+		assert!(bs.is_empty());
+	}
+
+	#[test]
+	fn isolate_lowest_set() {
+		let bs = BitSet::<[u8; 4]>::empty();
+		assert!(bs.isolate_lowest_set().is_empty());
+
+		let mut bs = BitSet::<[u8; 4]>::empty();
+		bs.set(12);
+		bs.set(13);
+		bs.set(30);
+
+		let lowest = bs.isolate_lowest_set();
+		assert_eq!(lowest.ones().collect::<Vec<_>>(), [12]);
+		assert_eq!(
+			BitSet::<[u8; 4]>::full()
+				.isolate_lowest_set()
+				.ones()
+				.collect::<Vec<_>>(),
+			[0]
+		);
+	}
+
+	#[test]
+	fn common_ones() {
+		let mut bs1 = BitSet::<[u64; 4]>::empty();
+		let mut bs2 = BitSet::<[u64; 4]>::empty();
+
+		for i in (0..256).step_by(3) {
+			bs1.set(i);
+		}
+
+		for i in (0..256).step_by(5) {
+			bs2.set(i);
+		}
+
+		assert!(bs1.common_ones(&bs2).eq(bs1.intersection(&bs2).ones()));
+		assert!(bs1.common_ones(&BitSet::empty()).next().is_none());
+	}
+
+	#[test]
+	fn retain_range() {
+		let mut bs = BitSet::<[u8; 4]>::full();
+		bs.retain_range(6..19);
+		assert!(bs.ones().eq(6..19));
+		assert_eq!(bs.get(5), Some(false));
+		assert_eq!(bs.get(6), Some(true));
+		assert_eq!(bs.get(18), Some(true));
+		assert_eq!(bs.get(19), Some(false));
+
+		let mut bs = BitSet::<[u8; 4]>::full();
+		bs.retain_range(8..16);
+		assert_eq!(bs, BitSet::from([0, !0, 0, 0]));
+
+		let mut bs = BitSet::<u32>::full();
+		bs.retain_range(0..32);
+		assert!(bs.is_full());
+
+		bs.retain_range(7..7);
+		assert!(bs.is_empty());
+	}
+
+	#[test]
+	#[should_panic(expected = "range end out of bounds")]
+	fn retain_range_out_of_bounds() {
+		BitSet::<[u8; 4]>::full().retain_range(0..33);
+	}
+
+	#[test]
+	fn shift_left() {
+		let mut bs = BitSet::<[u8; 3]>::empty();
+		bs.set(0);
+		bs.set(7);
+		bs.set(13);
+
+		let mut shifted = bs;
+		shifted.shift_left(9);
+		assert_eq!(shifted.ones().collect::<Vec<_>>(), [9, 16, 22]);
+
+		let mut shifted = bs;
+		shifted.shift_left(16);
+		assert_eq!(shifted.ones().collect::<Vec<_>>(), [16, 23]);
+
+		let mut shifted = bs;
+		shifted.shift_left(0);
+		assert_eq!(shifted, bs);
+
+		let mut shifted = bs;
+		shifted.shift_left(24);
+		assert!(shifted.is_empty());
+
+		let mut shifted = bs;
+		shifted.shift_left(u32::MAX);
+		assert!(shifted.is_empty());
+	}
+
+	#[test]
+	fn shift_right() {
+		let mut bs = BitSet::<[u8; 3]>::empty();
+		bs.set(9);
+		bs.set(16);
+		bs.set(23);
+
+		let mut shifted = bs;
+		shifted.shift_right(9);
+		assert_eq!(shifted.ones().collect::<Vec<_>>(), [0, 7, 14]);
+
+		let mut shifted = bs;
+		shifted.shift_right(16);
+		assert_eq!(shifted.ones().collect::<Vec<_>>(), [0, 7]);
+
+		let mut shifted = bs;
+		shifted.shift_right(0);
+		assert_eq!(shifted, bs);
+
+		let mut shifted = bs;
+		shifted.shift_right(24);
+		assert!(shifted.is_empty());
+
+		let mut shifted = bs;
+		shifted.shift_right(u32::MAX);
+		assert!(shifted.is_empty());
+	}
+
+	#[test]
+	fn push_low() {
+		let mut bs = BitSet::<[u8; 8]>::empty();
+		bs.push_low(0b1101, 4);
+		bs.push_low(0xABCD_EF01, 32);
+		bs.push_low(0, 0);
+		bs.push_low(0b10, 2);
+
+		let mut expected = BitSet::<[u8; 8]>::empty();
+		for i in (0..38).filter(|i| (0b1101_u64 << 34 | 0xABCD_EF01 << 2 | 0b10) >> i & 1 != 0) {
+			expected.set(i);
+		}
+
+		assert_eq!(bs, expected);
+
+		let mut bs = BitSet::from(0xFFu8);
+		bs.push_low(0b1010_1010_1010, 12);
+		assert_eq!(bs, BitSet::from(0b1010_1010u8));
+	}
+
+	#[test]
+	#[should_panic(expected = "count out of bounds")]
+	fn push_low_too_many_bits() {
+		BitSet::<[u64; 2]>::empty().push_low(0, 33);
+	}
+
+	#[test]
+	fn one_ranges() {
+		assert_eq!(
+			BitSet::from(0b0011_1100u8).one_ranges().collect::<Vec<_>>(),
+			vec![2..6]
+		);
+		assert_eq!(BitSet::from(0u8).one_ranges().count(), 0);
+		assert_eq!(
+			BitSet::<[u8; 3]>::full().one_ranges().collect::<Vec<_>>(),
+			vec![0..24]
+		);
+
+		let mut bs = BitSet::<[u8; 3]>::empty();
+		for i in [0].into_iter().chain(5..7).chain(8..16).chain(17..24) {
+			bs.set(i);
+		}
+
+		assert!(bs.one_ranges().eq([0..1, 5..7, 8..16, 17..24]));
+	}
+
+	#[test]
+	fn is_exactly() {
+		let mut bs = BitSet::<[u8; 2]>::empty();
+		assert!(bs.is_exactly(&[]));
+
+		bs.set(3);
+		bs.set(11);
+
+		assert!(bs.is_exactly(&[3, 11]));
+		assert!(bs.is_exactly(&[11, 3, 11]));
+		assert!(!bs.is_exactly(&[3]));
+		assert!(!bs.is_exactly(&[3, 3]));
+		assert!(!bs.is_exactly(&[3, 11, 12]));
+		assert!(!bs.is_exactly(&[3, 11, 16]));
+
+		bs.set(12);
+		assert!(!bs.is_exactly(&[3, 11]));
+	}
+
+	#[test]
+	fn map_words() {
+		let bs = BitSet::<[u8; 2]>::full();
+		let even = bs.map_words(|w| w & 0b0101_0101);
+		assert!(even.ones().eq((0..16).step_by(2)));
+
+		let mut seen = Vec::new();
+		let bs = BitSet::from([1u8, 2, 3]).map_words(|w| {
+			seen.push(w);
+			!w
+		});
+		assert_eq!(seen, [1, 2, 3]);
+		assert_eq!(bs, BitSet::from([!1u8, !2, !3]));
+
+		let bs = BitSet::from(0x00FF_FF00u32).map_words(|w| w.rotate_left(8));
+		assert_eq!(bs, BitSet::from(0xFFFF_0000u32));
+	}
+
+	#[test]
+	fn cmp_bits_matches_binary_strings() {
+		let values = [
+			[0u8, 0],
+			[1, 0],
+			[0, 1],
+			[0x80, 0],
+			[0, 0x80],
+			[0xFF, 0x7F],
+			[0x7F, 0xFF],
+			[0xFF, 0xFF],
+			[0x55, 0xAA],
+			[0xAA, 0x55],
+		];
+
+		for a in values.map(BitSet::from) {
+			for b in values.map(BitSet::from) {
+				assert_eq!(
+					a.cmp_bits(&b),
+					format!("{:b}", a).cmp(&format!("{:b}", b)),
+					"{:b} <=> {:b}",
+					a,
+					b
+				);
+			}
+		}
+
+		let a = BitSet::from(0x1234u16);
+		let b = BitSet::from(0x1243u16);
+		assert_eq!(a.cmp_bits(&b), a.cmp(&b));
+		assert_eq!(a.cmp_bits(&b), format!("{:b}", a).cmp(&format!("{:b}", b)));
+	}
+
+	#[test]
+	fn swap_bits() {
+		let mut bs = BitSet::<[u8; 2]>::empty();
+		bs.set(2);
+
+		assert_eq!(bs.swap_bits(2, 13), Some(()));
+		assert!(bs.is_exactly(&[13]));
+
+		bs.set(4);
+		let before = bs;
+		assert_eq!(bs.swap_bits(4, 13), Some(()));
+		assert_eq!(bs, before);
+
+		assert_eq!(bs.swap_bits(4, 4), Some(()));
+		assert_eq!(bs, before);
+
+		assert_eq!(bs.swap_bits(16, 4), None);
+		assert_eq!(bs.swap_bits(4, 16), None);
+		assert_eq!(bs, before);
+	}
+
+	#[test]
+	fn clear_above() {
+		let mut bs = BitSet::<[u8; 3]>::full();
+		bs.clear_above(9);
+		assert!(bs.ones().eq(0..10));
+
+		let mut bs = BitSet::<[u8; 3]>::full();
+		bs.clear_above(0);
+		assert!(bs.is_exactly(&[0]));
+
+		let mut bs = BitSet::<[u8; 3]>::full();
+		bs.clear_above(23);
+		assert!(bs.is_full());
+
+		bs.clear_above(u32::MAX);
+		assert!(bs.is_full());
+	}
+
+	#[test]
+	fn clear_below() {
+		let mut bs = BitSet::<[u8; 3]>::full();
+		bs.clear_below(9);
+		assert!(bs.ones().eq(9..24));
+
+		let mut bs = BitSet::<[u8; 3]>::full();
+		bs.clear_below(23);
+		assert!(bs.is_exactly(&[23]));
+
+		let mut bs = BitSet::<[u8; 3]>::full();
+		bs.clear_below(0);
+		assert!(bs.is_full());
+
+		bs.clear_below(24);
+		assert!(bs.is_empty());
+	}
+
+	#[test]
+	#[cfg(feature = "std")]
+	fn overlap_coefficient() {
+		let a = BitSet::<[u8; 2]>::from_iter([1u32, 4, 9, 12]);
+		let b = BitSet::<[u8; 2]>::from_iter([1u32, 9, 15]);
+		assert_eq!(a.overlap_coefficient(&b), 2.0 / 3.0);
+		assert_eq!(b.overlap_coefficient(&a), 2.0 / 3.0);
+
+		let c = BitSet::<[u8; 2]>::from_iter([4u32, 12]);
+		assert_eq!(a.overlap_coefficient(&c), 1.0);
+
+		let empty = BitSet::<[u8; 2]>::empty();
+		assert_eq!(empty.overlap_coefficient(&empty), 1.0);
+		assert_eq!(empty.overlap_coefficient(&a), 0.0);
+		assert_eq!(a.overlap_coefficient(&empty), 0.0);
+	}
+
+	#[test]
+	#[cfg(feature = "std")]
+	fn dice() {
+		let a = BitSet::<[u8; 2]>::from_iter([1u32, 4, 9, 12]);
+		let b = BitSet::<[u8; 2]>::from_iter([1u32, 9, 15]);
+		assert_eq!(a.dice(&b), 4.0 / 7.0);
+		assert_eq!(b.dice(&a), 4.0 / 7.0);
+		assert_eq!(a.dice(&a), 1.0);
+
+		let c = BitSet::<[u8; 2]>::from_iter([0u32, 15]);
+		assert_eq!(a.dice(&c), 0.0);
+
+		let empty = BitSet::<[u8; 2]>::empty();
+		assert_eq!(empty.dice(&empty), 1.0);
+		assert_eq!(empty.dice(&a), 0.0);
+	}
+
+	#[test]
+	fn leading_and_trailing_runs() {
+		let bs = BitSet::from([0b1111_1000u8, 0b0000_0011, 0]);
+		assert_eq!(bs.trailing_run(false), 3);
+		assert_eq!(bs.trailing_run(true), 0);
+		assert_eq!(bs.leading_run(false), 14);
+		assert_eq!(bs.leading_run(true), 0);
+
+		let bs = BitSet::from([0b0000_0111u8, 0, 0b1110_0000]);
+		assert_eq!(bs.trailing_run(true), 3);
+		assert_eq!(bs.trailing_run(false), 0);
+		assert_eq!(bs.leading_run(true), 3);
+		assert_eq!(bs.leading_run(false), 0);
+
+		let bs = BitSet::<[u8; 3]>::full();
+		assert_eq!(bs.trailing_run(true), 24);
+		assert_eq!(bs.leading_run(false), 0);
+	}
+
+	#[test]
+	fn prefix_and_suffix() {
+		let bs = BitSet::from(0b0000_1111u8);
+		assert!(bs.is_prefix());
+		assert!(!bs.is_suffix());
+
+		let bs = BitSet::from(0b1111_0000u8);
+		assert!(!bs.is_prefix());
+		assert!(bs.is_suffix());
+
+		let bs = BitSet::from(0b0101_0101u8);
+		assert!(!bs.is_prefix());
+		assert!(!bs.is_suffix());
+
+		assert!(BitSet::<u8>::empty().is_prefix());
+		assert!(BitSet::<u8>::empty().is_suffix());
+		assert!(BitSet::<u8>::full().is_prefix());
+		assert!(BitSet::<u8>::full().is_suffix());
+
+		let bs = BitSet::from([!0u8, 0b0000_0111, 0]);
+		assert!(bs.is_prefix());
+		assert!(!bs.is_suffix());
+
+		let bs = BitSet::from([0u8, 0b1110_0000, !0]);
+		assert!(!bs.is_prefix());
+		assert!(bs.is_suffix());
+
+		let bs = BitSet::from([!0u8, 0, !0]);
+		assert!(!bs.is_prefix());
+		assert!(!bs.is_suffix());
+	}
+
+	#[test]
+	fn spread() {
+		let bs = BitSet::from([0b1011_0110u8, 0b1000_0001]);
+		assert_eq!(bs.spread(1), bs);
+
+		let bs = BitSet::from([0b0000_1111u8, 0]);
+		assert_eq!(bs.spread(2), BitSet::from([0b0101_0101u8, 0]));
+
+		let bs = BitSet::from([0b1001_0011u8, 0b0000_0001]);
+		assert_eq!(bs.spread(2), BitSet::from([0b0000_0101u8, 0b0100_0001]));
+
+		let bs = BitSet::from(0b1000_0011u8);
+		assert_eq!(bs.spread(3), BitSet::from(0b0000_1001u8));
+		assert_eq!(bs.spread(u32::MAX), BitSet::from(0b0000_0001u8));
+	}
+
+	#[test]
+	fn gather() {
+		let cases = [
+			(0x1234_5678u32, 0xFF00_FFF0u32, 0x0001_2567u32),
+			(0xFFFF_FFFF, 0x8000_0001, 0b11),
+			(0x8000_0000, 0x8000_0001, 0b10),
+			(0xDEAD_BEEF, 0xFFFF_FFFF, 0xDEAD_BEEF),
+			(0xDEAD_BEEF, 0, 0),
+			(0b1011_0110, 0b1010_1010, 0b1101),
+		];
+
+		for (value, mask, expected) in cases {
+			assert_eq!(
+				BitSet::from(value).gather(&BitSet::from(mask)),
+				BitSet::from(expected),
+				"pext({:#x}, {:#x})",
+				value,
+				mask
+			);
+		}
+
+		let bs = BitSet::from([0b1010_1010u8, 0b0000_1111]);
+		let mask = BitSet::from([0b1100_0000u8, 0b0000_0011]);
+		assert_eq!(bs.gather(&mask), BitSet::from([0b1110u8, 0]));
+	}
+
+	#[test]
+	fn scatter() {
+		let cases = [
+			(0x0001_2567u32, 0xFF00_FFF0u32, 0x1200_5670u32),
+			(0b11, 0x8000_0001, 0x8000_0001),
+			(0b10, 0x8000_0001, 0x8000_0000),
+			(0xDEAD_BEEF, 0xFFFF_FFFF, 0xDEAD_BEEF),
+			(0xDEAD_BEEF, 0, 0),
+			(0b1001, 0b1010_1010, 0b1000_0010),
+		];
+
+		for (value, mask, expected) in cases {
+			assert_eq!(
+				BitSet::from(value).scatter(&BitSet::from(mask)),
+				BitSet::from(expected),
+				"pdep({:#x}, {:#x})",
+				value,
+				mask
+			);
+		}
+
+		let bs = BitSet::from([0b1110u8, 0]);
+		let mask = BitSet::from([0b1100_0000u8, 0b0000_0011]);
+		assert_eq!(
+			bs.scatter(&mask),
+			BitSet::from([0b1000_0000u8, 0b0000_0011])
+		);
+	}
+
+	#[cfg(feature = "arbitrary")]
+	#[test]
+	fn arbitrary_is_deterministic() {
+		use arbitrary::{Arbitrary, Unstructured};
+
+		let bytes = [0b0000_0101u8, 0x00, 0xff, 0b1000_0000];
+		let a = BitSet::<[u8; 4]>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+		let b = BitSet::<[u8; 4]>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+
+		assert_eq!(a, b);
+		assert_eq!(a, BitSet::from(bytes));
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn serde_round_trip() {
+		let word = BitSet::from(0x8000_0000_0000_0501u64);
+		let json = serde_json::to_string(&word).unwrap();
+		assert_eq!(json, "9223372036854777089");
+		assert_eq!(serde_json::from_str::<BitSet<u64>>(&json).unwrap(), word);
+
+		let bytes = bincode::serialize(&word).unwrap();
+		assert_eq!(bincode::deserialize::<BitSet<u64>>(&bytes).unwrap(), word);
+
+		let array = BitSet::from([0b0000_0101u8, 0x00, 0xff, 0b1000_0000]);
+		let json = serde_json::to_string(&array).unwrap();
+		assert_eq!(json, "[5,0,255,128]");
+		assert_eq!(
+			serde_json::from_str::<BitSet<[u8; 4]>>(&json).unwrap(),
+			array
+		);
+
+		let bytes = bincode::serialize(&array).unwrap();
+		assert_eq!(bytes, [5, 0, 255, 128]);
+		assert_eq!(
+			bincode::deserialize::<BitSet<[u8; 4]>>(&bytes).unwrap(),
+			array
+		);
+	}
+
+	#[test]
+	fn bounding_range_spans_gaps() {
+		let mut bs = BitSet::from([0u8; 4]);
+		bs.set(2);
+		bs.set(9);
+		bs.set(27);
+		assert_eq!(bs.bounding_range(), Some(2..28));
+	}
+
+	#[test]
+	fn bounding_range_single_bit() {
+		let mut bs = BitSet::from([0u16; 2]);
+		bs.set(17);
+		assert_eq!(bs.bounding_range(), Some(17..18));
+		assert_eq!(BitSet::from(u32::MAX).bounding_range(), Some(0..32));
+	}
+
+	#[test]
+	fn bool_array_store() {
+		let mut a = BitSet::from([false; 16]);
+		a.set(1);
+		a.set(9);
+		a.set(15);
+		assert_eq!(a.get(9), Some(true));
+		assert_eq!(a.get(10), Some(false));
+		assert!(a.bits[9]);
+
+		let mut b = BitSet::from([false; 16]);
+		b.set(2);
+		b.set(9);
+
+		let union = a.union(&b);
+		assert_eq!(union.ones().collect::<Vec<_>>(), [1, 2, 9, 15]);
+		assert_eq!(union.bits.count_ones(), 4);
+		assert_eq!(a.intersection(&b).ones().collect::<Vec<_>>(), [9]);
+
+		a.shift_left(3);
+		assert_eq!(a.ones().collect::<Vec<_>>(), [4, 12]);
+	}
+
+	#[test]
+	fn dilate_single_bit() {
+		let mut bs = BitSet::from([0u8; 3]);
+		bs.set(8);
+		assert_eq!(bs.dilate(1).ones().collect::<Vec<_>>(), [7, 8, 9]);
+		assert_eq!(bs.dilate(2).ones().collect::<Vec<_>>(), [6, 7, 8, 9, 10]);
+		assert_eq!(bs.dilate(0), bs);
+		assert!(bs.dilate(100).is_full());
+	}
+
+	#[test]
+	fn dilate_at_edges() {
+		let bs = BitSet::from(0b1000_0001u8);
+		assert_eq!(bs.dilate(1), BitSet::from(0b1100_0011u8));
+	}
+
+	#[test]
+	fn erode_shrinks_runs() {
+		let mut bs = BitSet::from([0u8; 3]);
+		for i in 5..12 {
+			bs.set(i);
+		}
+		assert_eq!(bs.erode(1).ones().collect::<Vec<_>>(), [6, 7, 8, 9, 10]);
+		assert_eq!(bs.erode(3).ones().collect::<Vec<_>>(), [8]);
+		assert!(bs.erode(4).is_empty());
+
+		let mut single = BitSet::from([0u8; 3]);
+		single.set(8);
+		assert!(single.erode(1).is_empty());
+		assert_eq!(single.dilate(1).erode(1), single);
+	}
+
+	#[cfg(feature = "rand")]
+	#[test]
+	fn random_k_sets_exactly_k_bits() {
+		use rand::{rngs::SmallRng, SeedableRng};
+
+		let mut rng = SmallRng::seed_from_u64(42);
+		for k in [0, 1, 7, 32, 63, 64] {
+			let bs = BitSet::<[u16; 4]>::random_k(&mut rng, k);
+			assert_eq!(bs.bits.count_ones(), k);
+		}
+
+		assert!(BitSet::<[u16; 4]>::random_k(&mut rng, 65).is_full());
+		assert!(BitSet::<u8>::random_k(&mut rng, u32::MAX).is_full());
+	}
+
+	#[test]
+	fn xor_with_value_on_arrays() {
+		let mut bs = BitSet::from([0b1010u8, 0xff]);
+		bs.xor_with_value([0b0110, 0x0f]);
+		assert_eq!(bs, BitSet::from([0b1100u8, 0xf0]));
+
+		bs.xor_assign_store(&[0b1100, 0xf0]);
+		assert!(bs.is_empty());
+	}
+
+	#[test]
+	fn run_stats_alternating() {
+		let stats = BitSet::from([0x55u8; 4]).run_stats();
+		assert_eq!(stats.runs, 32);
+		assert_eq!(stats.one_runs, 16);
+		assert_eq!(stats.zero_runs, 16);
+		assert_eq!(stats.longest_one_run, 1);
+		assert_eq!(stats.longest_zero_run, 1);
+	}
+
+	#[test]
+	fn run_stats_single_block() {
+		let mut bs = BitSet::from([0u8; 4]);
+		for i in 6..20 {
+			bs.set(i);
+		}
+
+		let stats = bs.run_stats();
+		assert_eq!(stats.runs, 3);
+		assert_eq!(stats.one_runs, 1);
+		assert_eq!(stats.zero_runs, 2);
+		assert_eq!(stats.longest_one_run, 14);
+		assert_eq!(stats.longest_zero_run, 12);
+	}
+
+	#[test]
+	fn run_stats_empty_and_full() {
+		let empty = BitSet::from([0u16; 2]).run_stats();
+		assert_eq!(
+			empty,
+			RunStats {
+				runs: 1,
+				one_runs: 0,
+				zero_runs: 1,
+				longest_one_run: 0,
+				longest_zero_run: 32,
+			}
+		);
+
+		let full = BitSet::from([u16::MAX; 2]).run_stats();
+		assert_eq!(
+			full,
+			RunStats {
+				runs: 1,
+				one_runs: 1,
+				zero_runs: 0,
+				longest_one_run: 32,
+				longest_zero_run: 0,
+			}
+		);
+	}
+
+	#[test]
+	fn select_from_end_matches_highest_set() {
+		let mut bs = BitSet::from([0u8; 4]);
+		for i in [1, 5, 9, 17, 30] {
+			bs.set(i);
+		}
+
+		let highest = bs.bounding_range().unwrap().end - 1;
+		assert_eq!(bs.select_from_end(0), Some(highest));
+		assert_eq!(
+			(0..5)
+				.map(|n| bs.select_from_end(n).unwrap())
+				.collect::<Vec<_>>(),
+			[30, 17, 9, 5, 1]
+		);
+		assert_eq!(bs.select_from_end(5), None);
+		assert_eq!(bs.select_from_end(u32::MAX), None);
+		assert_eq!(BitSet::from([0u8; 4]).select_from_end(0), None);
+	}
+
+	#[test]
+	fn word_sets_reassemble() {
+		let mut bs = BitSet::from([[0u8; 2]; 3]);
+		for i in [0, 9, 20, 33, 47] {
+			bs.set(i);
+		}
+
+		let sets = bs.word_sets().collect::<Vec<_>>();
+		assert_eq!(sets.len(), 3);
+		assert_eq!(sets[2].ones().collect::<Vec<_>>(), [1, 15]);
+
+		let mut words = [[0u8; 2]; 3];
+		for (word, set) in words.iter_mut().zip(sets) {
+			*word = set.bits;
+		}
+		assert_eq!(BitSet::from(words), bs);
+	}
+
+	#[test]
+	fn msb_first_reverses_indices() {
+		let mut bs = BitSet::from([0u16; 3]);
+		bs.set(0);
+		bs.set(20);
+
+		let msb = bs.msb_first();
+		assert_eq!(msb.get(47), Some(true));
+		assert_eq!(msb.get(27), Some(true));
+		assert_eq!(msb.get(0), Some(false));
+		assert_eq!(msb.bits.count_ones(), bs.bits.count_ones());
+		assert_eq!(msb.lsb_first(), bs);
+	}
+
+	#[test]
+	fn cursor_reads_header_and_payload() {
+		let mut bs = BitSet::from([0u8; 2]);
+		// header 0b1010, payload 0b1100_0011
+		for i in [1, 3, 4, 5, 10, 11] {
+			bs.set(i);
+		}
+
+		let mut cursor = bs.cursor();
+		assert_eq!(cursor.remaining(), 16);
+		assert_eq!(cursor.take_bits(4), 0b1010);
+		assert_eq!(cursor.position(), 4);
+		assert_eq!(cursor.take_bits(8), 0b1100_0011);
+		assert_eq!(cursor.position(), 12);
+		assert_eq!(cursor.remaining(), 4);
+
+		assert_eq!(cursor.peek(), Some(false));
+		assert_eq!(cursor.position(), 12);
+		assert_eq!(cursor.by_ref().take(2).filter(|&b| b).count(), 0);
+		assert_eq!(cursor.position(), 14);
+
+		// only two bits are left
+		assert_eq!(cursor.take_bits(32), 0);
+		assert_eq!(cursor.remaining(), 0);
+		assert_eq!(cursor.peek(), None);
+	}
+
+	#[test]
+	#[should_panic(expected = "count out of bounds: the max is 32 but the count is 33")]
+	fn cursor_take_bits_too_many() {
+		BitSet::from(0u64).cursor().take_bits(33);
+	}
+
+	#[test]
+	fn from_sorted_matches_from_iter() {
+		let indices = [0, 3, 17, 31, 40, 47];
+		let expected = indices.iter().copied().collect::<BitSet<[u16; 3]>>();
+		assert_eq!(BitSet::from_sorted(indices), Some(expected));
+		assert_eq!(
+			unsafe { BitSet::<[u16; 3]>::from_sorted_unchecked(indices) },
+			expected
+		);
+		assert_eq!(
+			BitSet::<[u16; 3]>::from_sorted([]),
+			Some(BitSet::from([0; 3]))
+		);
+	}
+
+	#[test]
+	fn from_sorted_rejects_invalid_input() {
+		assert_eq!(BitSet::<[u16; 3]>::from_sorted([0, 0]), None);
+		assert_eq!(BitSet::<[u16; 3]>::from_sorted([5, 3]), None);
+		assert_eq!(BitSet::<[u16; 3]>::from_sorted([3, 48]), None);
+		assert_eq!(
+			BitSet::<[u16; 3]>::from_sorted([47]).map(|bs| bs.get(47)),
+			Some(Some(true))
+		);
+	}
+
+	#[test]
+	fn slice_across_words() {
+		let mut bs = BitSet::from([0u8; 3]);
+		for i in [3, 6, 9, 14, 20] {
+			bs.set(i);
+		}
+
+		let slice = bs.slice(5..15);
+		assert_eq!(slice.ones().collect::<Vec<_>>(), [1, 4, 9]);
+		assert_eq!(bs.slice(0..24), bs);
+	}
+
+	#[test]
+	fn slice_empty_range() {
+		let bs = BitSet::from([u8::MAX; 3]);
+		assert!(bs.slice(7..7).is_empty());
+		assert!(bs.slice(24..24).is_empty());
+	}
+
+	#[test]
+	#[should_panic(expected = "range end out of bounds: the len is 24 but the end is 25")]
+	fn slice_out_of_bounds() {
+		BitSet::from([0u8; 3]).slice(0..25);
+	}
+
+	#[test]
+	fn pack_unpack_low_round_trip() {
+		for count in [0, 1, 7, 8, 13, 31, 32] {
+			let value = 0xdead_beef_u32 & ((1u64 << count) - 1) as u32;
+			let mut bs = BitSet::from([0u8; 6]);
+			bs.unpack_low(value, count);
+			assert_eq!(bs.pack_low(count), value, "count {}", count);
+			assert_eq!(bs.bits.count_ones(), value.count_ones());
+		}
+	}
+
+	#[test]
+	fn unpack_low_keeps_higher_bits() {
+		let mut bs = BitSet::from([u8::MAX; 2]);
+		bs.unpack_low(0, 12);
+		assert_eq!(bs, BitSet::from([0, 0xf0]));
+		assert_eq!(bs.pack_low(32), 0xf000);
+	}
+
+	#[test]
+	#[should_panic(expected = "count out of bounds: the max is 32 but the count is 33")]
+	fn pack_low_too_many() {
+		BitSet::from(0u64).pack_low(33);
+	}
+
+	#[test]
+	fn from_checked_logical_width() {
+		assert!(BitSet::from_checked(0x000f_ffffu32, 20).is_some());
+		assert!(BitSet::from_checked(0u32, 20).is_some());
+		assert!(BitSet::from_checked(0x0010_0000u32, 20).is_none());
+		assert!(BitSet::from_checked(0x8000_0001u32, 20).is_none());
+		assert!(BitSet::from_checked(u32::MAX, 32).is_some());
+		assert!(BitSet::from_checked(u32::MAX, 40).is_some());
+		assert!(BitSet::from_checked(1u32, 0).is_none());
+
+		let mut bits = [0u8; 4];
+		bits[2] = 0b0001_0000;
+		assert!(BitSet::from_checked(bits, 20).is_none());
+		assert!(BitSet::from_checked(bits, 21).is_some());
+	}
+
+	#[test]
+	fn export_chunks_groups_by_chunk() {
+		let mut bs = BitSet::from([0u64; 2]);
+		for i in [1, 15, 70, 75, 127] {
+			bs.set(i);
+		}
+
+		assert!(bs
+			.export_chunks()
+			.eq([(0, 1), (0, 15), (4, 6), (4, 11), (7, 15)]));
+		assert_eq!(BitSet::from([0u64; 2]).export_chunks().count(), 0);
+	}
+
+	#[test]
+	fn export_chunks_last_chunk() {
+		let mut bs = BitSet::from([0u64; 16385]);
+		bs.set((1 << 20) - 1);
+		assert!(bs.export_chunks().eq([(u16::MAX, 15)]));
+	}
+
+	#[test]
+	#[should_panic(expected = "bit index out of range for 16-bit chunks")]
+	fn export_chunks_past_last_chunk() {
+		let mut bs = BitSet::from([0u64; 16385]);
+		bs.set(1 << 20);
+		bs.export_chunks().for_each(drop);
+	}
+
+	#[test]
+	fn combine_with_every_truth_table() {
+		let a = BitSet::from([0b1100_1010u8, 0x0f, 0xff]);
+		let b = BitSet::from([0b1010_0110u8, 0xf0, 0x00]);
+
+		for table in 0u8..16 {
+			let op = |x: bool, y: bool| table & (1 << ((x as u8) << 1 | y as u8)) != 0;
+			let mut combined = a;
+			combined.combine_with(&b, op);
+
+			for i in 0..a.len() {
+				assert_eq!(
+					combined[i],
+					op(a[i], b[i]),
+					"table {:04b}, bit {}",
+					table,
+					i
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn combine_with_implication() {
+		let a = BitSet::from([0b1100_1010u8, 0x0f, 0xff]);
+		let b = BitSet::from([0b1010_0110u8, 0xf0, 0x00]);
+
+		let mut implication = a;
+		implication.combine_with(&b, |a, b| !a | b);
+
+		for i in 0..a.len() {
+			let expected = !a[i] | b[i];
+			assert_eq!(implication[i], expected, "bit {}", i);
+		}
+
+		let mut union = a;
+		union.combine_with(&b, |a, b| a | b);
+		assert_eq!(union, a.union(&b));
+
+		let mut nor = a;
+		nor.combine_with(&b, |a, b| !(a | b));
+		assert_eq!(nor, a.union(&b).negation());
+	}
+
+	#[test]
+	fn correlate_peaks_at_separation() {
+		let mut a = BitSet::from([0u8; 3]);
+		a.set(17);
+		let mut b = BitSet::from([0u8; 3]);
+		b.set(4);
+
+		let correlation = a.correlate(&b).collect::<Vec<_>>();
+		assert_eq!(correlation.len(), 47);
+		assert_eq!(correlation.first().map(|c| c.0), Some(-23));
+		assert_eq!(correlation.last().map(|c| c.0), Some(23));
+		assert!(correlation
+			.iter()
+			.all(|&(offset, overlap)| overlap == (offset == 13) as u32));
+
+		let reverse = b.correlate(&a).max_by_key(|&(_, overlap)| overlap);
+		assert_eq!(reverse, Some((-13, 1)));
+	}
+
+	#[test]
+	fn blit_across_words() {
+		let mut bs = BitSet::from([0u8, 0, 0, 0x80]);
+		bs.blit(&BitSet::from(0b1000_0101u8), 10);
+		assert_eq!(bs.ones().collect::<Vec<_>>(), [10, 12, 17, 31]);
+
+		// bits past the end are dropped
+		bs.blit(&BitSet::from(0xffu8), 28);
+		assert_eq!(bs.ones().collect::<Vec<_>>(), [10, 12, 17, 28, 29, 30, 31]);
+		bs.blit(&BitSet::from(0xffu8), u32::MAX);
+		assert_eq!(bs.bits.count_ones(), 7);
+	}
+
+	#[test]
+	fn take_leaves_empty() {
+		let mut bs = BitSet::from([0u16; 3]);
+		bs.set(4);
+		bs.set(40);
+
+		let old = bs.take();
+		assert!(bs.is_empty());
+		assert_eq!(old.ones().collect::<Vec<_>>(), [4, 40]);
 
-		bs2.set(7);
-		bs2.set(9);
+		assert!(bs.take().is_empty());
+	}
 
-		bs1.intersect_with(&bs2);
+	#[test]
+	fn extent_of_outer_words() {
+		let mut bs = BitSet::from([0u64; 4]);
+		bs.set(5);
+		bs.set(130);
+		bs.set(250);
+		assert_eq!(bs.extent(), Some((5, 250)));
 
-		assert!(!bs1[3]);
-		assert!(bs1[7]);
-		assert!(!bs1[9]);
+		let mut single = BitSet::from([0u64; 4]);
+		single.set(200);
+		assert_eq!(single.extent(), Some((200, 200)));
+		assert_eq!(BitSet::from([0u64; 4]).extent(), None);
+		assert_eq!(BitSet::from([u64::MAX; 4]).extent(), Some((0, 255)));
+	}
+
+	#[test]
+	fn rank_with_matches_direct_count() {
+		let bs = BitSet::from([0b1011_0110u8, 0b0000_0001, 0b1111_0000]);
+		let prefix = bs.prefix_popcounts();
+		assert_eq!(prefix, [0, 5, 6]);
+
+		for index in 0..=bs.len() {
+			let direct = bs.ones().take_while(|&i| i < index).count() as u32;
+			assert_eq!(bs.rank_with(&prefix, index), direct, "index {}", index);
+		}
+
+		let empty = BitSet::<[u8; 0]>::from([]);
+		assert_eq!(empty.rank_with(&empty.prefix_popcounts(), 0), 0);
+	}
+
+	#[test]
+	fn elements_yields_indices() {
+		let mut bs = BitSet::from([0u8; 3]);
+		for i in [0, 9, 23] {
+			bs.set(i);
+		}
+
+		assert_eq!(bs.elements().collect::<Vec<_>>(), [0, 9, 23]);
+		assert_eq!(bs.elements().rev().collect::<Vec<_>>(), [23, 9, 0]);
+
+		let mut elements = bs.elements();
+		assert_eq!(elements.next(), Some(0));
+		assert_eq!(elements.next_back(), Some(23));
+		assert_eq!(elements.next(), Some(9));
+		assert_eq!(elements.next_back(), None);
+		assert_eq!(elements.next(), None);
+	}
+
+	#[test]
+	fn shift_overflow_chains_sets() {
+		// three sets acting as one 48-bit shift register, lowest first
+		let mut sets = [
+			BitSet::from(0b1000_0000_0000_0001u16),
+			BitSet::from(0b0100_0000_0000_0000u16),
+			BitSet::from(0u16),
+		];
+
+		let mut carry = BitSet::from(0u16);
+		for set in sets.iter_mut() {
+			let overflow = set.shift_left_overflowing(5);
+			set.union_with(&carry);
+			carry = overflow;
+		}
+		assert!(carry.is_empty());
+		let ones = sets
+			.iter()
+			.enumerate()
+			.flat_map(|(i, s)| s.ones().map(move |b| i as u32 * 16 + b))
+			.collect::<Vec<_>>();
+		assert_eq!(ones, [5, 20, 35]);
+
+		let mut carry = BitSet::from(0u16);
+		for set in sets.iter_mut().rev() {
+			let overflow = set.shift_right_overflowing(5);
+			set.union_with(&carry);
+			carry = overflow;
+		}
+		assert!(carry.is_empty());
+		assert_eq!(sets[0], BitSet::from(0b1000_0000_0000_0001u16));
+		assert_eq!(sets[1], BitSet::from(0b0100_0000_0000_0000u16));
+	}
+
+	#[test]
+	fn shift_overflowing_by_more_than_len() {
+		let mut bs = BitSet::from(0b0000_0011u8);
+		assert_eq!(bs.shift_left_overflowing(8), BitSet::from(0b0000_0011u8));
+		assert!(bs.is_empty());
+
+		let mut bs = BitSet::from(0b0000_0011u8);
+		assert_eq!(bs.shift_left_overflowing(10), BitSet::from(0b0000_1100u8));
+		let mut bs = BitSet::from(0b1100_0000u8);
+		assert_eq!(bs.shift_right_overflowing(10), BitSet::from(0b0011_0000u8));
+	}
+
+	#[test]
+	fn ones_array_requires_exact_count() {
+		let mut bs = BitSet::from([0u16; 4]);
+		for i in [2, 17, 40, 63] {
+			bs.set(i);
+		}
+
+		assert_eq!(bs.ones_array::<4>(), Some([2, 17, 40, 63]));
+		assert_eq!(bs.ones_array::<3>(), None);
+		assert_eq!(bs.ones_array::<5>(), None);
+		assert_eq!(BitSet::from([0u16; 4]).ones_array::<0>(), Some([]));
+	}
+
+	#[test]
+	fn xor3_matches_chained_symmetric_difference() {
+		let a = BitSet::from([0x0123_4567_89ab_cdefu64, 0xffff_0000_ffff_0000]);
+		let b = BitSet::from([0xdead_beef_dead_beefu64, 0x0f0f_0f0f_0f0f_0f0f]);
+		let c = BitSet::from([0x1111_2222_3333_4444u64, 0x8000_0000_0000_0001]);
+
+		let chained = a.symmetric_difference(&b).symmetric_difference(&c);
+		assert_eq!(BitSet::xor3(&a, &b, &c), chained);
+		assert_eq!(BitSet::xor3(&a, &a, &c), c);
+	}
+
+	#[test]
+	fn transitions_at_word_boundary() {
+		assert_eq!(BitSet::from(0u8).transitions(), 0);
+		assert_eq!(BitSet::from(0b1010u8).transitions(), 4);
+		assert_eq!(BitSet::from(0b0101u8).transitions(), 3);
+		assert_eq!(BitSet::from([0u8, 0xff]).transitions(), 1);
+		assert_eq!(BitSet::from([0xffu8, 0]).transitions(), 1);
+		assert_eq!(BitSet::from([0x55u8; 4]).transitions(), 31);
+		assert_eq!(BitSet::from([false, true, true, false]).transitions(), 2);
+	}
+
+	#[test]
+	fn lane_mask_matches_get() {
+		let bs = BitSet::from(0b1001_0110u8);
+		let mask = bs.lane_mask::<8>();
+		for (i, lane) in mask.into_iter().enumerate() {
+			assert_eq!(Some(lane), bs.get(i as u32), "lane {}", i);
+		}
+
+		let wide = bs.lane_mask::<12>();
+		assert_eq!(wide[..8], mask);
+		assert_eq!(wide[8..], [false; 4]);
+	}
+
+	#[test]
+	fn ones_from_matches_filtered_ones() {
+		let mut bs = BitSet::from([0u16; 3]);
+		for i in [0, 1, 15, 16, 30, 47] {
+			bs.set(i);
+		}
+
+		for k in 0..=50 {
+			assert!(
+				bs.ones_from(k).eq(bs.ones().filter(|&i| i >= k)),
+				"start {}",
+				k
+			);
+		}
+	}
+
+	#[test]
+	fn full_default_is_full() {
+		let bs = FullDefault::<[u16; 3]>::default();
+		assert!(bs.is_full());
+		assert_eq!(bs.0, BitSet::all_set());
+
+		#[derive(Default)]
+		struct Config {
+			features: FullDefault<u8>,
+		}
+		assert!(Config::default().features.is_full());
+	}
+
+	#[test]
+	fn diff_reports_new_values() {
+		let mut old = BitSet::from([0u8; 3]);
+		let mut new = BitSet::from([0u8; 3]);
+		for i in [1, 8, 15, 20] {
+			old.set(i);
+		}
+		for i in [1, 9, 15, 23] {
+			new.set(i);
+		}
+
+		assert_eq!(
+			new.diff(&old).collect::<Vec<_>>(),
+			[(8, false), (9, true), (20, false), (23, true)]
+		);
+		assert_eq!(
+			old.diff(&new).collect::<Vec<_>>(),
+			[(8, true), (9, false), (20, true), (23, false)]
+		);
+		assert_eq!(new.diff(&new).count(), 0);
+	}
+
+	#[test]
+	fn count_ones_where_matches_intersection() {
+		let bs = BitSet::from([0x0123_4567_89ab_cdefu64, 0xdead_beef_dead_beef]);
+		let mask = BitSet::from([0xffff_0000_ffff_0000u64, 0x0f0f_0f0f_f0f0_f0f0]);
+		assert_eq!(
+			bs.count_ones_where(&mask),
+			bs.intersection(&mask).bits.count_ones()
+		);
+		assert_eq!(bs.count_ones_where(&BitSet::full()), bs.bits.count_ones());
+		assert_eq!(bs.count_ones_where(&BitSet::empty()), 0);
+	}
+
+	#[test]
+	fn shift_with_fill_both_directions() {
+		let bs = BitSet::from([0b1000_0001u8, 0b0001_0000]);
+
+		let mut shifted = bs;
+		shifted.shift_with_fill(3, false);
+		assert_eq!(shifted, BitSet::from([0b0000_1000u8, 0b1000_0100]));
+		let mut shifted = bs;
+		shifted.shift_with_fill(3, true);
+		assert_eq!(shifted, BitSet::from([0b0000_1111u8, 0b1000_0100]));
+		let mut shifted = bs;
+		shifted.shift_with_fill(10, true);
+		assert_eq!(shifted, BitSet::from([0xffu8, 0b0000_0111]));
+
+		let mut shifted = bs;
+		shifted.shift_with_fill(-3, false);
+		assert_eq!(shifted, BitSet::from([0b0001_0000u8, 0b0000_0010]));
+		let mut shifted = bs;
+		shifted.shift_with_fill(-3, true);
+		assert_eq!(shifted, BitSet::from([0b0001_0000u8, 0b1110_0010]));
+
+		let mut shifted = bs;
+		shifted.shift_with_fill(0, true);
+		assert_eq!(shifted, bs);
+	}
+
+	#[test]
+	fn shift_with_fill_past_the_end() {
+		for by in [16, 17, i32::MAX, -16, -100, i32::MIN] {
+			let mut shifted = BitSet::from([0b1010_0101u8, 0x0f]);
+			shifted.shift_with_fill(by, true);
+			assert!(shifted.is_full(), "by {}", by);
+
+			shifted.shift_with_fill(by, false);
+			assert!(shifted.is_empty(), "by {}", by);
+		}
+	}
+
+	#[test]
+	fn to_bool_array_exact_width() {
+		let mut bs = BitSet::from([0u8; 2]);
+		for i in [0, 7, 8, 13] {
+			bs.set(i);
+		}
+
+		assert_eq!(bs.to_bool_array::<15>(), None);
+		assert_eq!(bs.to_bool_array::<17>(), None);
+
+		let bools = bs.to_bool_array::<16>().unwrap();
+		let round_trip = BitSet::from(bools);
+		assert!(round_trip.ones().eq(bs.ones()));
+	}
+
+	#[test]
+	fn union_ones_merges_sorted_and_deduplicated() {
+		let mut sets = [BitSet::from([0u64; 4]); 3];
+		for (set, indices) in
+			sets
+				.iter_mut()
+				.zip([&[3, 100, 200][..], &[3, 64, 255][..], &[0, 100, 199][..]])
+		{
+			for &i in indices {
+				set.set(i);
+			}
+		}
+
+		assert_eq!(
+			iter::union_ones(&sets).collect::<Vec<_>>(),
+			[0, 3, 64, 100, 199, 200, 255]
+		);
+		assert_eq!(iter::union_ones(&sets).take(2).collect::<Vec<_>>(), [0, 3]);
+		assert_eq!(iter::union_ones::<u8>(&[]).count(), 0);
+	}
+
+	#[test]
+	fn count_zeros_complements_count_ones() {
+		fn check<S: BitStore>(bs: BitSet<S>) {
+			assert_eq!(bs.bits.count_ones() + bs.count_zeros(), S::BITS);
+		}
+
+		check(BitSet::from(0b1011_0010u8));
+		check(BitSet::from([0b1111_0000u8, 0b0000_0001]));
+		check(BitSet::from(core::array::from_fn::<u64, 10, _>(|i| {
+			(i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+		})));
+		assert_eq!(BitSet::<[u64; 10]>::empty().count_zeros(), 640);
+		assert_eq!(BitSet::<[u8; 2]>::full().count_zeros(), 0);
+	}
+
+	#[test]
+	fn rank_at_word_boundaries() {
+		let mut bs = BitSet::from([0u64; 3]);
+		for i in [0, 63, 64, 127, 128, 191] {
+			bs.set(i);
+		}
+
+		assert_eq!(bs.rank(0), 0);
+		assert_eq!(bs.rank(63), 1);
+		assert_eq!(bs.rank(64), 2);
+		assert_eq!(bs.rank(65), 3);
+		assert_eq!(bs.rank(128), 4);
+		assert_eq!(bs.rank(192), bs.count_ones());
+		assert_eq!(bs.rank(1000), bs.count_ones());
+
+		let full = BitSet::<u32>::full();
+		assert_eq!(full.rank(0), 0);
+		assert_eq!(full.rank(31), 31);
+		assert_eq!(full.rank(32), 32);
+	}
+
+	#[test]
+	fn first_and_last_one_across_stores() {
+		let mut bs = BitSet::from(0u8);
+		assert_eq!((bs.first_one(), bs.last_one()), (None, None));
+		bs.set(0);
+		assert_eq!((bs.first_one(), bs.last_one()), (Some(0), Some(0)));
+		bs.set(7);
+		assert_eq!((bs.first_one(), bs.last_one()), (Some(0), Some(7)));
+
+		let mut bs = BitSet::from([0u64; 10]);
+		bs.set(639);
+		assert_eq!((bs.first_one(), bs.last_one()), (Some(639), Some(639)));
+		bs.set(64);
+		assert_eq!((bs.first_one(), bs.last_one()), (Some(64), Some(639)));
+		assert_eq!(BitSet::<[u64; 10]>::full().last_one(), Some(639));
+	}
+
+	#[test]
+	fn first_and_last_zero_across_stores() {
+		let full = BitSet::<u8>::full();
+		assert_eq!((full.first_zero(), full.last_zero()), (None, None));
+
+		let empty = BitSet::<[u32; 3]>::empty();
+		assert_eq!((empty.first_zero(), empty.last_zero()), (Some(0), Some(95)));
+
+		let mut bs = BitSet::<[u32; 3]>::full();
+		bs.unset(40);
+		assert_eq!((bs.first_zero(), bs.last_zero()), (Some(40), Some(40)));
+		bs.unset(32);
+		bs.unset(63);
+		assert_eq!((bs.first_zero(), bs.last_zero()), (Some(32), Some(63)));
+	}
+
+	#[test]
+	fn next_set_bit_crosses_words() {
+		let mut bs = BitSet::from([0u32; 4]);
+		for i in [5, 32, 33, 70, 127] {
+			bs.set(i);
+		}
+
+		assert_eq!(bs.next_set_bit(0), Some(5));
+		assert_eq!(bs.next_set_bit(6), Some(32));
+		assert_eq!(bs.next_set_bit(32), Some(32));
+		assert_eq!(bs.next_set_bit(34), Some(70));
+		assert_eq!(bs.next_set_bit(64), Some(70));
+		assert_eq!(bs.next_set_bit(71), Some(127));
+		assert_eq!(bs.next_set_bit(128), None);
+		assert_eq!(bs.next_set_bit(u32::MAX), None);
+
+		bs.unset(127);
+		assert_eq!(bs.next_set_bit(96), None);
+	}
+
+	#[test]
+	fn subset_and_disjoint_match_clone_based() {
+		use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+		let mut rng = SmallRng::seed_from_u64(508);
+		for _ in 0..200 {
+			// Sparse words make subsets and disjoint pairs likely enough
+			// to exercise both outcomes.
+			let mut sparse = || -> [u64; 10] {
+				core::array::from_fn(|_| rng.gen::<u64>() & rng.gen::<u64>() & rng.gen::<u64>())
+			};
+			let a = BitSet::from(sparse());
+			let b = BitSet::from(sparse());
+			let ab = a.union(&b);
+
+			for (x, y) in [(a, b), (a, ab), (ab, a), (a, -b), (a, a)] {
+				assert_eq!(x.is_subset(&y), x.difference(&y).is_empty());
+				assert_eq!(x.is_superset(&y), y.difference(&x).is_empty());
+				assert_eq!(x.is_disjoint(&y), x.intersection(&y).is_empty());
+			}
+		}
+	}
+
+	#[test]
+	fn set_operation_counts_agree() {
+		let bs1 = BitSet::from([0b1011_0110u16, 0xff00, 0x0001]);
+		let bs2 = BitSet::from([0b0110_0011u16, 0x0ff0, 0x8001]);
+
+		let both = bs1.intersection_count(&bs2);
+		assert_eq!(
+			bs1.union_count(&bs2),
+			bs1.count_ones() + bs2.count_ones() - both
+		);
+		assert_eq!(bs1.difference_count(&bs2), bs1.count_ones() - both);
+		assert_eq!(
+			bs1.symmetric_difference_count(&bs2),
+			bs1.union_count(&bs2) - both
+		);
+
+		assert_eq!(bs1.union_count(&bs2), bs1.union(&bs2).count_ones());
+		assert_eq!(
+			bs1.difference_count(&bs2),
+			bs1.difference(&bs2).count_ones()
+		);
+		assert_eq!(
+			bs1.symmetric_difference_count(&bs2),
+			bs1.symmetric_difference(&bs2).count_ones()
+		);
+	}
+
+	#[test]
+	fn hamming_distance_of_arrays() {
+		let bs = BitSet::from([0x0f0fu16, 0x00ff, 0xffff]);
+		assert_eq!(bs.hamming_distance(&bs), 0);
+		assert_eq!(bs.hamming_distance(&-bs), 48);
+		assert_eq!(bs.hamming_distance(&BitSet::empty()), bs.count_ones());
+		assert_eq!(bs.hamming_distance(&BitSet::full()), bs.count_zeros());
+	}
+
+	#[test]
+	fn jaccard_fractions() {
+		let bs1 = BitSet::from([0b0000_1111u8, 0b1000_0000]);
+		let bs2 = BitSet::from([0b1111_0000u8, 0b0000_0001]);
+		assert_eq!(bs1.jaccard(&bs2), (0, 10));
+		assert_eq!(bs1.jaccard(&bs1), (5, 5));
+		assert_eq!(bs1.jaccard(&bs1.union(&bs2)), (5, 10));
+
+		let empty = BitSet::<[u8; 2]>::empty();
+		assert_eq!(empty.jaccard(&empty), (0, 0));
+		assert_eq!(empty.jaccard(&bs1), (0, 5));
+	}
+
+	#[test]
+	fn clear_and_fill_in_place() {
+		let mut bs = BitSet::<[u64; 3]>::full();
+		bs.clear();
+		assert!(bs.is_empty());
+		bs.fill();
+		assert!(bs.is_full());
+
+		let mut words = [0xf0u8; 2];
+		let mut borrowed = BitSet::from(&mut words);
+		borrowed.fill();
+		assert_eq!(words, [0xff; 2]);
+	}
+
+	#[test]
+	fn set_and_unset_ranges() {
+		let mut bs = BitSet::from([0u32; 4]);
+		bs.set_range(3..9);
+		assert!(bs.ones().eq(3..9));
+
+		bs.set_range(30..100);
+		assert!(bs.ones().eq((3..9).chain(30..100)));
+
+		bs.unset_range(32..96);
+		assert!(bs.ones().eq((3..9).chain(30..32).chain(96..100)));
+
+		let before = bs;
+		bs.set_range(50..50);
+		bs.unset_range(128..128);
+		assert_eq!(bs, before);
+
+		bs.set_range(0..128);
+		assert!(bs.is_full());
+		bs.unset_range(0..128);
+		assert!(bs.is_empty());
+
+		let mut msb = BitSet::from(MsbFirst([0u8; 2]));
+		msb.set_range(6..10);
+		assert_eq!(msb.lsb_first(), BitSet::from([0b1100_0000u8, 0b0000_0011]));
+	}
+
+	#[test]
+	#[should_panic(expected = "range end out of bounds: the len is 16 but the end is 17")]
+	fn set_range_out_of_bounds() {
+		BitSet::from([0u8; 2]).set_range(4..17);
+	}
+
+	#[test]
+	fn toggle_range_flips_each_bit() {
+		let original = BitSet::from([0b1001_0110u8, 0b0110_1001]);
+		let mut bs = original;
+		bs.toggle_range(4..12);
+		for i in 0..16 {
+			assert_eq!(bs[i], original[i] != (4..12).contains(&i), "bit {}", i);
+		}
+
+		bs.toggle_range(7..7);
+		bs.toggle_range(16..16);
+		bs.toggle_range(4..12);
+		assert_eq!(bs, original);
+	}
+
+	#[test]
+	fn count_ones_in_range_matches_filter() {
+		let bs = BitSet::from([0xdead_beefu32, 0x0000_ffff, 0x8000_0001]);
+		for range in [0..0, 0..96, 3..17, 31..33, 32..64, 40..95, 95..96, 96..96] {
+			let expected = bs.ones().filter(|i| range.contains(i)).count() as u32;
+			assert_eq!(
+				bs.count_ones_in_range(range.clone()),
+				expected,
+				"{:?}",
+				range
+			);
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "range start is greater than range end")]
+	fn count_ones_in_reversed_range() {
+		#[allow(clippy::reversed_empty_ranges)]
+		BitSet::from(0u8).count_ones_in_range(5..3);
+	}
+
+	#[test]
+	fn zeros_complement_ones() {
+		let bs = BitSet::from([0x0f0fu16, 0xfffe, 0x8000]);
+		let mut all = bs.ones().chain(bs.zeros()).collect::<Vec<_>>();
+		all.sort_unstable();
+		assert!(all.into_iter().eq(0..48));
+
+		assert!(bs.zeros().eq(bs.into_zeros()));
+		assert!(bs.zeros().rev().eq(bs.into_zeros().rev()));
+		assert!(BitSet::<[u8; 4]>::full().zeros().next().is_none());
+		assert!(BitSet::<[u8; 4]>::empty().into_zeros().eq(0..32));
+	}
+
+	#[test]
+	fn zeros_skips_full_words() {
+		let mut bs = BitSet::from([u64::MAX; 16]);
+		for i in [0, 63, 64, 700, 1023] {
+			bs.unset(i);
+		}
+
+		assert!(bs.zeros().eq([0, 63, 64, 700, 1023]));
+		assert!(bs.zeros().rev().eq([1023, 700, 64, 63, 0]));
+		assert_eq!(bs.zeros().len(), 5);
+
+		let mut zeros = bs.zeros();
+		assert_eq!(zeros.next(), Some(0));
+		assert_eq!(zeros.next_back(), Some(1023));
+		assert_eq!(zeros.next_back(), Some(700));
+		assert!(zeros.eq([63, 64]));
+	}
+
+	#[test]
+	fn ones_skips_empty_words() {
+		let mut bs = BitSet::from([0u64; 16]);
+		for i in [0, 63, 64, 700, 1023] {
+			bs.set(i);
+		}
+
+		assert!(bs.ones().eq([0, 63, 64, 700, 1023]));
+		assert!(bs.ones().rev().eq([1023, 700, 64, 63, 0]));
+		assert!(bs.ones().eq(
+			bs.iter()
+				.enumerate()
+				.filter_map(|(i, b)| b.then_some(i as u32))
+		));
+		assert!(BitSet::from([0u64; 16]).ones().next().is_none());
+
+		let mut ones = bs.ones();
+		assert_eq!(ones.next(), Some(0));
+		assert_eq!(ones.next_back(), Some(1023));
+		assert_eq!(ones.next_back(), Some(700));
+		assert_eq!(ones.next(), Some(63));
+		assert_eq!(ones.next(), Some(64));
+		assert_eq!(ones.next_back(), None);
+		assert_eq!(ones.next(), None);
+	}
+
+	#[test]
+	fn named_ones_iterators_interleave() {
+		struct Cursor<'a> {
+			ones: iter::Ones<&'a [u16; 4]>,
+		}
+
+		let mut bs = BitSet::from([0u16; 4]);
+		for i in [1, 2, 17, 40, 41, 63] {
+			bs.set(i);
+		}
+
+		let mut cursor = Cursor { ones: bs.ones() };
+		assert_eq!(cursor.ones.len(), 6);
+		assert_eq!(cursor.ones.next_back(), Some(63));
+		assert_eq!(cursor.ones.next(), Some(1));
+		assert_eq!(cursor.ones.len(), 4);
+		assert_eq!(cursor.ones.next_back(), Some(41));
+		assert_eq!(cursor.ones.next(), Some(2));
+		assert_eq!(cursor.ones.next(), Some(17));
+		assert_eq!(cursor.ones.next_back(), Some(40));
+		assert_eq!(cursor.ones.len(), 0);
+		assert_eq!(cursor.ones.next(), None);
+		assert_eq!(cursor.ones.next_back(), None);
+
+		let mut into: iter::IntoOnes<[u16; 4]> = bs.into_ones();
+		assert_eq!(into.len(), 6);
+		assert_eq!(into.next_back(), Some(63));
+		assert_eq!(into.next(), Some(1));
+		assert_eq!(into.next_back(), Some(41));
+		assert_eq!(into.len(), 3);
+		assert!(into.eq([2, 17, 40]));
+	}
+
+	#[test]
+	fn runs_edge_cases() {
+		let mut bs = BitSet::from([0u8; 3]);
+		bs.set_range(20..24);
+		bs.set_range(3..7);
+		bs.set_range(8..17);
+		assert!(bs.runs().eq([3..7, 8..17, 20..24]));
+
+		bs.set(7);
+		assert!(bs.runs().eq([3..17, 20..24]));
+
+		assert_eq!(
+			BitSet::<[u8; 3]>::full().runs().collect::<Vec<_>>(),
+			vec![0..24]
+		);
+		assert!(BitSet::<[u8; 3]>::empty().runs().next().is_none());
+	}
+
+	#[test]
+	fn runs_and_gaps_partition_bits() {
+		let mut bs = BitSet::from([0u16; 3]);
+		for range in [0..3, 5..6, 15..33, 40..47] {
+			bs.set_range(range);
+		}
+
+		let mut ranges = bs.runs().chain(bs.gaps()).collect::<Vec<_>>();
+		ranges.sort_unstable_by_key(|r| r.start);
+		assert_eq!(ranges.first().map(|r| r.start), Some(0));
+		assert_eq!(ranges.last().map(|r| r.end), Some(48));
+		for pair in ranges.windows(2) {
+			assert_eq!(pair[0].end, pair[1].start);
+		}
+
+		assert_eq!(
+			BitSet::<[u16; 3]>::empty().gaps().collect::<Vec<_>>(),
+			vec![0..48]
+		);
+		assert!(BitSet::<[u16; 3]>::full().gaps().next().is_none());
+	}
+
+	#[test]
+	fn collect_indices() {
+		let bs: BitSet<u32> = [3u32, 7, 11, 7].into_iter().collect();
+		assert_eq!(bs, BitSet::from(0b1000_1000_1000u32));
+
+		let bs = (0..40u32).step_by(13).collect::<BitSet<[u8; 5]>>();
+		assert!(bs.ones().eq([0, 13, 26, 39]));
+
+		let err = [1u32, 32].into_iter().try_collect::<BitSet<u32>>();
+		assert_eq!(
+			err.map_err(|e| format!("{}", e)),
+			Err("index 32 out of range for array of length 32".into())
+		);
+	}
+
+	#[test]
+	#[should_panic(expected = "index 32 out of range for array of length 32")]
+	fn collect_out_of_range_index() {
+		let _: BitSet<u32> = [3u32, 32].into_iter().collect();
+	}
+
+	#[test]
+	fn extend_with_indices() {
+		let mut bs = BitSet::from([0u8; 2]);
+		bs.extend([9u32, 2, 9]);
+		let once = bs;
+		bs.extend([9u32, 9]);
+		assert_eq!(bs, once);
+
+		bs.extend([15u32, 2]);
+		assert!(bs.ones().eq([2, 9, 15]));
+		assert_eq!(bs.count_ones(), 3);
+
+		bs.extend([0u8, 1]);
+		bs.extend([14usize]);
+		assert!(bs.ones().eq([0, 1, 2, 9, 14, 15]));
+	}
+
+	#[test]
+	#[should_panic(expected = "index 16 out of range for array of length 16")]
+	fn extend_with_out_of_range_index() {
+		let mut bs = BitSet::from([0u8; 2]);
+		bs.extend([15u32, 16]);
+	}
+
+	#[test]
+	fn collect_bools_round_trip() {
+		let bs = BitSet::from(0b1001_0110_0000_1101u16);
+		assert_eq!(bs.into_iter().collect::<BitSet<u16>>(), bs);
+
+		let arr = BitSet::from([0xa5u8, 0x3c, 0x01]);
+		assert_eq!(arr.into_iter().collect::<BitSet<[u8; 3]>>(), arr);
+
+		let mut items = core::iter::repeat_n(true, 20);
+		let full = items.by_ref().collect::<BitSet<u16>>();
+		assert!(full.is_full());
+		assert_eq!(items.len(), 4);
+
+		let mut bs = BitSet::from(0b1000u8);
+		bs.extend([true, false]);
+		assert_eq!(bs, BitSet::from(0b1001u8));
+	}
+
+	#[test]
+	fn from_fn_visits_every_index() {
+		let mut visited = Vec::new();
+		let bs = BitSet::<[u8; 3]>::from_fn(|i| {
+			visited.push(i);
+			i >= 20
+		});
+		assert!(visited.into_iter().eq(0..24));
+		assert!(bs.ones().eq(20..24));
+	}
+
+	#[test]
+	fn retain_odd_indices() {
+		let mut bs = BitSet::<u32>::full();
+		let mut calls = 0;
+		bs.retain(|i| {
+			calls += 1;
+			i % 2 == 1
+		});
+		assert_eq!(calls, 32);
+		assert!(bs.ones().eq((1..32).step_by(2)));
+
+		let mut sparse = BitSet::from([0u64; 4]);
+		sparse.extend([3u32, 64, 200]);
+		let mut seen = Vec::new();
+		sparse.retain(|i| {
+			seen.push(i);
+			i != 64
+		});
+		assert_eq!(seen, [3, 64, 200]);
+		assert!(sparse.ones().eq([3, 200]));
+	}
+
+	#[test]
+	fn display_lists_indices() {
+		let bs = BitSet::from([0u8, 0b1000_0001, 0]);
+		assert_eq!(format!("{}", bs), "{8, 15}");
+		assert_eq!(
+			format!("{}", BitSet::<u8>::full()),
+			"{0, 1, 2, 3, 4, 5, 6, 7}"
+		);
+		assert_eq!(format!("{:?}", bs), format!("{:b}", bs));
+	}
+
+	#[test]
+	fn parse_binary_strings() {
+		let bs = BitSet::from(0b1011_0010u8);
+		assert_eq!("0b_1011_0010".parse(), Ok(bs));
+		assert_eq!("10110010".parse(), Ok(bs));
+		assert_eq!("1011__0010_".parse(), Ok(bs));
+
+		let wide = BitSet::from([0x1234_5678u32, 0x9abc_def0, 0x0f0f_0f0f]);
+		assert_eq!(format!("{:b}", wide).parse(), Ok(wide));
+
+		assert_eq!(
+			"0b1011".parse::<BitSet<u8>>(),
+			Err(ParseBitSetError::WrongLength {
+				expected: 8,
+				found: 4
+			})
+		);
+		assert_eq!(
+			"0b1_0110_0101".parse::<BitSet<u8>>(),
+			Err(ParseBitSetError::WrongLength {
+				expected: 8,
+				found: 9
+			})
+		);
+		assert_eq!(
+			"0b1011_0012".parse::<BitSet<u8>>(),
+			Err(ParseBitSetError::InvalidDigit('2'))
+		);
+		assert_eq!(
+			"0x10110010".parse::<BitSet<u8>>(),
+			Err(ParseBitSetError::InvalidDigit('x'))
+		);
+		assert_eq!(
+			format!("{}", ParseBitSetError::InvalidDigit('x')),
+			"invalid binary digit 'x'"
+		);
+	}
+
+	#[test]
+	fn bytes_round_trip() {
+		let mut bs = BitSet::<[u8; 4]>::empty();
+		bs.set(0);
+		bs.set(8);
+		assert_eq!(bs.to_bytes(), [0x01, 0x01, 0x00, 0x00]);
+		assert_eq!(BitSet::from_bytes(bs.to_bytes()), bs);
+
+		let bytes = [0xde, 0xad, 0xbe, 0xef, 0x01];
+		assert_eq!(BitSet::from_bytes(bytes).to_bytes(), bytes);
+		assert_eq!(
+			BitSet::from_bytes(0x1234_5678u32.to_le_bytes())
+				.ones()
+				.collect::<Vec<_>>(),
+			BitSet::from(0x1234_5678u32).ones().collect::<Vec<_>>()
+		);
+	}
+
+	#[test]
+	fn try_from_bool_slice() {
+		let bools = (0..24).map(|i| i % 5 == 0).collect::<Vec<_>>();
+		let bs = BitSet::<[u8; 3]>::try_from(bools.as_slice()).unwrap();
+		assert!(bs.ones().eq((0..24).step_by(5)));
+		assert!(bs.iter().eq(bools.iter().copied()));
+
+		let short = BitSet::<[u8; 3]>::try_from(&bools[..23]);
+		assert_eq!(
+			short.map_err(|e| format!("{}", e)),
+			Err("expected a slice of length 24 but found 23".into())
+		);
+
+		let long = [false; 25];
+		assert!(BitSet::<[u8; 3]>::try_from(&long[..]).is_err());
+		assert!(BitSet::<[u8; 0]>::try_from(&long[..0]).unwrap().is_empty());
+	}
+
+	#[test]
+	fn union_ones_dense_and_sparse() {
+		let dense = BitSet::<[u64; 64]>::full();
+		let mut sparse = BitSet::<[u64; 64]>::empty();
+		sparse.set(4095);
+		sparse.set(7);
+
+		let sets = [sparse, dense];
+		assert!(iter::union_ones(&sets).eq(0..4096));
+
+		let mut evens = BitSet::<[u64; 64]>::from_fn(|i| i % 2 == 0);
+		evens.unset(4094);
+		let sets = [evens, sparse];
+		assert!(iter::union_ones(&sets).eq(
+			(0..4094)
+				.step_by(2)
+				.chain([7, 4095])
+				.collect::<alloc::collections::BTreeSet<_>>()
+		));
 	}
 }
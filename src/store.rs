@@ -1,19 +1,80 @@
 //! Traits for types that can be used to store bits.
 
+use core::cell::Cell;
+use core::mem::MaybeUninit;
+use core::ops;
+
 /// A trait for types that can be used to store bits.
 pub trait BitStore {
 	/// The number of bits that can be stored in this type.
 	const BITS: u32;
 
+	/// The primitive word type the bits are stored in.
+	type Word: BitWord;
+
+	/// The number of words backing this type.
+	const WORDS: u32;
+
 	/// Returns the bit at the given index.
 	///
 	/// # Safety
 	/// The index must be in range 0..[Self::BITS].
 	unsafe fn get(&self, index: u32) -> bool;
 
+	/// Returns the word at the given index. Bit `i` of word `w` is the
+	/// bit at index `w * Self::Word::BITS + i` of this type.
+	///
+	/// # Safety
+	/// The index must be in range 0..[Self::WORDS].
+	unsafe fn word(&self, index: u32) -> Self::Word;
+
+	/// Returns the bit at the given index as `0` or `1`, for use in
+	/// arithmetic.
+	///
+	/// # Safety
+	/// The index must be in range 0..[Self::BITS].
+	#[inline]
+	unsafe fn bit(&self, index: u32) -> u32 {
+		self.get(index) as u32
+	}
+
 	/// Returns the number of bits set to 1.
 	fn count_ones(&self) -> u32;
 
+	/// Returns the number of bits set to 0.
+	#[inline]
+	fn count_zeros(&self) -> u32 {
+		Self::BITS - self.count_ones()
+	}
+
+	/// Returns the number of bits set to 1 at indices below `index`.
+	///
+	/// # Safety
+	/// The index must be in range 0..=[Self::BITS].
+	#[inline]
+	unsafe fn rank(&self, index: u32) -> u32 {
+		(0..Self::WORDS)
+			.map(|i| {
+				let word = self.word(i);
+				(word & range_word_mask(&(0..index), i)).count_ones()
+			})
+			.sum()
+	}
+
+	/// Returns the number of bits set to 1 in both this bitset and `other`.
+	#[inline]
+	fn intersection_count(&self, other: &Self) -> u32 {
+		count_ones_with(self, other, |a, b| a & b)
+	}
+
+	/// Returns the index of the first bit at or after `from` that is set to
+	/// 1, or `None` if there is none. Returns `None` if `from` is out of
+	/// bounds.
+	#[inline]
+	fn next_one(&self, from: u32) -> Option<u32> {
+		scan_words(self, from, |w| w)
+	}
+
 	/// Returns the number of trailing bits set to 0.
 	fn trailing_zeros(&self) -> u32;
 
@@ -37,6 +98,17 @@ pub trait BitStore {
 	fn is_full(&self) -> bool {
 		self.count_ones() == Self::BITS
 	}
+
+	/// Folds the backing words into an accumulator, from the lowest word to
+	/// the highest. Words wider than `usize` are passed to `f` in
+	/// `usize`-sized pieces, lowest first.
+	#[inline]
+	fn fold_words<B, F: FnMut(B, usize) -> B>(&self, init: B, mut f: F) -> B {
+		(0..Self::WORDS).fold(init, |acc, i| {
+			// SAFETY: `i` is in range 0..Self::WORDS.
+			unsafe { self.word(i) }.fold_words(acc, &mut f)
+		})
+	}
 }
 
 /// A trait for types that can be used to store bits and can be modified.
@@ -53,6 +125,12 @@ pub trait BitStoreMut: BitStore {
 	/// The index must be in range 0..[BitStore::BITS].
 	unsafe fn unset(&mut self, index: u32);
 
+	/// Replaces the word at the given index.
+	///
+	/// # Safety
+	/// The index must be in range 0..[BitStore::WORDS].
+	unsafe fn set_word(&mut self, index: u32, word: Self::Word);
+
 	/// Unions this bitset with another, modifying `self` in place.
 	fn union_with(&mut self, other: &Self);
 
@@ -67,6 +145,89 @@ pub trait BitStoreMut: BitStore {
 
 	/// Negates this bitset, modifying `self` in place.
 	fn negate(&mut self);
+
+	/// Sets every bit to 0.
+	#[inline]
+	fn clear(&mut self) {
+		for i in 0..Self::WORDS {
+			// SAFETY: `i` is in range 0..Self::WORDS.
+			unsafe { self.set_word(i, Self::Word::EMPTY) };
+		}
+	}
+
+	/// Sets every bit to 1.
+	#[inline]
+	fn fill(&mut self) {
+		for i in 0..Self::WORDS {
+			// SAFETY: `i` is in range 0..Self::WORDS.
+			unsafe { self.set_word(i, Self::Word::FULL) };
+		}
+	}
+
+	/// Sets every bit in `range` to 1.
+	///
+	/// # Safety
+	/// The end of the range must be at most [BitStore::BITS].
+	#[inline]
+	unsafe fn set_range(&mut self, range: ops::Range<u32>) {
+		update_range_words(self, &range, |word, mask| word | mask)
+	}
+
+	/// Sets every bit in `range` to 0.
+	///
+	/// # Safety
+	/// The end of the range must be at most [BitStore::BITS].
+	#[inline]
+	unsafe fn unset_range(&mut self, range: ops::Range<u32>) {
+		update_range_words(self, &range, |word, mask| word & !mask)
+	}
+
+	/// Flips every bit in `range`.
+	///
+	/// # Safety
+	/// The end of the range must be at most [BitStore::BITS].
+	#[inline]
+	unsafe fn toggle_range(&mut self, range: ops::Range<u32>) {
+		update_range_words(self, &range, |word, mask| word ^ mask)
+	}
+
+	/// Returns `true` if every bit set in this bitset is also set in
+	/// `other`. Stops at the first word that has a bit `other` lacks.
+	#[inline]
+	fn is_subset_of(&self, other: &Self) -> bool {
+		(0..Self::WORDS).all(|i| {
+			// SAFETY: `i` is in range 0..Self::WORDS.
+			let (a, b) = unsafe { (self.word(i), other.word(i)) };
+			(a & !b).is_empty()
+		})
+	}
+
+	/// Returns `true` if no bit is set in both this bitset and `other`.
+	/// Stops at the first word the two have in common.
+	#[inline]
+	fn is_disjoint_from(&self, other: &Self) -> bool {
+		(0..Self::WORDS).all(|i| {
+			// SAFETY: `i` is in range 0..Self::WORDS.
+			let (a, b) = unsafe { (self.word(i), other.word(i)) };
+			(a & b).is_empty()
+		})
+	}
+}
+
+/// A trait for types that can be used to store bits and can be modified
+/// through a shared reference.
+pub trait BitStoreShared: BitStore {
+	/// Sets the bit at the given index to 1.
+	///
+	/// # Safety
+	/// The index must be in range 0..[BitStore::BITS].
+	unsafe fn set_shared(&self, index: u32);
+
+	/// Sets the bit at the given index to 0.
+	///
+	/// # Safety
+	/// The index must be in range 0..[BitStore::BITS].
+	unsafe fn unset_shared(&self, index: u32);
 }
 
 /// A trait for types that can be used to store bits and have constants for
@@ -74,6 +235,122 @@ pub trait BitStoreMut: BitStore {
 pub trait BitStoreConst: BitStore + Sized {
 	const EMPTY: Self;
 	const FULL: Self;
+
+	/// Creates a store from an iterator of words.
+	///
+	/// Integer stores take as many words as it takes to cover their width,
+	/// lowest first, truncating the last one if it is wider than needed. This
+	/// is the inverse of [BitStore::fold_words]. Array stores fill their
+	/// elements in order, padding with zeros if the iterator runs out and
+	/// ignoring any extra words.
+	fn from_words<I: IntoIterator<Item = usize>>(iter: I) -> Self;
+}
+
+/// A trait for the primitive unsigned integers that stores are made up of.
+pub trait BitWord:
+	BitStoreConst
+	+ BitStoreMut<Word = Self>
+	+ Copy
+	+ Ord
+	+ ops::BitAnd<Output = Self>
+	+ ops::BitOr<Output = Self>
+	+ ops::BitXor<Output = Self>
+	+ ops::Not<Output = Self>
+{
+	/// Shifts the bits of the word towards higher indices. `by` must be
+	/// less than [BitStore::BITS].
+	fn shl_word(self, by: u32) -> Self;
+
+	/// Shifts the bits of the word towards lower indices. `by` must be
+	/// less than [BitStore::BITS].
+	fn shr_word(self, by: u32) -> Self;
+
+	/// Reverses the order of the bits in the word.
+	fn reverse_bits(self) -> Self;
+}
+
+/// Returns a mask of the bits of the word at `word_index` that fall inside `range`.
+#[inline]
+pub(crate) fn range_word_mask<W: BitWord>(range: &ops::Range<u32>, word_index: u32) -> W {
+	let offset = word_index * W::BITS;
+	let start = range.start.clamp(offset, offset + W::BITS) - offset;
+	let end = range.end.clamp(offset, offset + W::BITS) - offset;
+	if start >= end {
+		W::EMPTY
+	} else {
+		W::FULL.shr_word(W::BITS - (end - start)).shl_word(start)
+	}
+}
+
+/// Returns the number of set bits in the word-wise combination of `a` and
+/// `b` by `f`.
+#[inline]
+pub(crate) fn count_ones_with<S: BitStore + ?Sized>(
+	a: &S,
+	b: &S,
+	f: impl Fn(S::Word, S::Word) -> S::Word,
+) -> u32 {
+	(0..S::WORDS)
+		.map(|i| {
+			// SAFETY: `i` is in range 0..S::WORDS.
+			let (a, b) = unsafe { (a.word(i), b.word(i)) };
+			f(a, b).count_ones()
+		})
+		.sum()
+}
+
+/// Returns the index of the first bit at or after `from` that is set in the
+/// words of `store` after applying `map` to them.
+#[inline]
+pub(crate) fn scan_words<S: BitStore + ?Sized>(
+	store: &S,
+	from: u32,
+	map: impl Fn(S::Word) -> S::Word,
+) -> Option<u32> {
+	if from >= S::BITS {
+		return None;
+	}
+
+	let word_bits = <S::Word as BitStore>::BITS;
+	let mut i = from / word_bits;
+	// SAFETY: `from` is in bounds, so `i` is in range 0..S::WORDS.
+	let mut word = map(unsafe { store.word(i) }) & range_word_mask(&(from..S::BITS), i);
+	loop {
+		if !word.is_empty() {
+			return Some(i * word_bits + word.trailing_zeros());
+		}
+
+		i += 1;
+		if i >= S::WORDS {
+			return None;
+		}
+
+		// SAFETY: `i` is in range 0..S::WORDS.
+		word = map(unsafe { store.word(i) });
+	}
+}
+
+/// Replaces each word of `store` that overlaps `range` with `f(word, mask)`,
+/// where `mask` has the bits of the word inside `range` set. Words entirely
+/// outside of `range` are not touched.
+///
+/// # Safety
+/// The end of the range must be at most `S::BITS`.
+#[inline]
+pub(crate) unsafe fn update_range_words<S: BitStoreMut + ?Sized>(
+	store: &mut S,
+	range: &ops::Range<u32>,
+	f: impl Fn(S::Word, S::Word) -> S::Word,
+) {
+	if range.start >= range.end {
+		return;
+	}
+
+	let word_bits = <S::Word as BitStore>::BITS;
+	for i in range.start / word_bits..=(range.end - 1) / word_bits {
+		let mask = range_word_mask(range, i);
+		store.set_word(i, f(store.word(i), mask));
+	}
 }
 
 /// A trait for types that have all bits set to 0 when they are created.
@@ -94,14 +371,50 @@ macro_rules! impl_bitstore_uint {
 	($ty:ty) => {
 		unsafe impl DefaultIsEmpty for $ty {}
 
+		impl BitWord for $ty {
+			#[inline]
+			fn shl_word(self, by: u32) -> Self {
+				self << by
+			}
+
+			#[inline]
+			fn shr_word(self, by: u32) -> Self {
+				self >> by
+			}
+
+			#[inline]
+			fn reverse_bits(self) -> Self {
+				<$ty>::reverse_bits(self)
+			}
+		}
+
 		impl BitStoreConst for $ty {
 			const EMPTY: Self = 0;
 			const FULL: Self = !0;
+
+			#[inline]
+			fn from_words<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+				let mut iter = iter.into_iter();
+				let mut value: $ty = 0;
+				let mut offset = 0;
+				while offset < Self::BITS {
+					match iter.next() {
+						Some(word) => value |= (word as $ty) << offset,
+						None => break,
+					}
+					offset += usize::BITS;
+				}
+
+				value
+			}
 		}
 
 		impl BitStore for $ty {
 			const BITS: u32 = core::mem::size_of::<$ty>() as u32 * 8;
 
+			type Word = $ty;
+			const WORDS: u32 = 1;
+
 			#[inline]
 			unsafe fn get(&self, index: u32) -> bool {
 				#[cfg(debug_assertions)]
@@ -116,11 +429,54 @@ macro_rules! impl_bitstore_uint {
 				(1 << index) & *self != 0
 			}
 
+			#[inline]
+			unsafe fn word(&self, index: u32) -> Self::Word {
+				#[cfg(debug_assertions)]
+				if index >= Self::WORDS {
+					panic!(
+						"index out of bounds: the len is {} but the index is {}",
+						Self::WORDS,
+						index
+					);
+				}
+
+				*self
+			}
+
 			#[inline]
 			fn count_ones(&self) -> u32 {
 				<$ty>::count_ones(*self)
 			}
 
+			#[inline]
+			fn count_zeros(&self) -> u32 {
+				<$ty>::count_zeros(*self)
+			}
+
+			#[inline]
+			unsafe fn rank(&self, index: u32) -> u32 {
+				if index >= <$ty>::BITS {
+					<$ty>::count_ones(*self)
+				} else {
+					<$ty>::count_ones(*self & ((1 << index) - 1))
+				}
+			}
+
+			#[inline]
+			fn intersection_count(&self, other: &Self) -> u32 {
+				<$ty>::count_ones(*self & *other)
+			}
+
+			#[inline]
+			fn next_one(&self, from: u32) -> Option<u32> {
+				if from >= <$ty>::BITS {
+					return None;
+				}
+
+				let masked = *self & (<$ty>::MAX << from);
+				(masked != 0).then(|| masked.trailing_zeros())
+			}
+
 			#[inline]
 			fn trailing_zeros(&self) -> u32 {
 				<$ty>::trailing_zeros(*self)
@@ -150,6 +506,18 @@ macro_rules! impl_bitstore_uint {
 			fn is_full(&self) -> bool {
 				*self == !0
 			}
+
+			#[inline]
+			fn fold_words<B, F: FnMut(B, usize) -> B>(&self, init: B, mut f: F) -> B {
+				let mut acc = init;
+				let mut offset = 0;
+				while offset < Self::BITS {
+					acc = f(acc, (*self >> offset) as usize);
+					offset += usize::BITS;
+				}
+
+				acc
+			}
 		}
 
 		impl BitStoreMut for $ty {
@@ -181,6 +549,20 @@ macro_rules! impl_bitstore_uint {
 				*self &= !(1 << index);
 			}
 
+			#[inline]
+			unsafe fn set_word(&mut self, index: u32, word: Self::Word) {
+				#[cfg(debug_assertions)]
+				if index >= Self::WORDS {
+					panic!(
+						"index out of bounds: the len is {} but the index is {}",
+						Self::WORDS,
+						index
+					);
+				}
+
+				*self = word;
+			}
+
 			#[inline]
 			fn union_with(&mut self, other: &Self) {
 				*self |= *other
@@ -205,6 +587,26 @@ macro_rules! impl_bitstore_uint {
 			fn negate(&mut self) {
 				*self = !*self
 			}
+
+			#[inline]
+			fn clear(&mut self) {
+				*self = 0
+			}
+
+			#[inline]
+			fn fill(&mut self) {
+				*self = !0
+			}
+
+			#[inline]
+			fn is_subset_of(&self, other: &Self) -> bool {
+				*self & !*other == 0
+			}
+
+			#[inline]
+			fn is_disjoint_from(&self, other: &Self) -> bool {
+				*self & *other == 0
+			}
 		}
 	};
 }
@@ -216,227 +618,996 @@ impl_bitstore_uint!(u64);
 impl_bitstore_uint!(u128);
 impl_bitstore_uint!(usize);
 
-impl<T: BitStoreConst, const N: usize> BitStoreConst for [T; N] {
-	const EMPTY: Self = [T::EMPTY; N];
-	const FULL: Self = [T::FULL; N];
-}
-
-impl<T: BitStore, const N: usize> BitStore for [T; N] {
-	const BITS: u32 = N as u32 * T::BITS;
+// SAFETY: `bool::default()` is `false`.
+unsafe impl DefaultIsEmpty for bool {}
 
+/// A single bit, mostly useful as the element of a `[bool; N]` store.
+impl BitWord for bool {
 	#[inline]
-	unsafe fn get(&self, index: u32) -> bool {
-		let (i, j) = (index / T::BITS, index % T::BITS);
-		self[i as usize].get(j)
+	fn shl_word(self, by: u32) -> Self {
+		self && by == 0
 	}
 
 	#[inline]
-	fn count_ones(&self) -> u32 {
-		self.iter().map(|x| x.count_ones()).sum()
+	fn shr_word(self, by: u32) -> Self {
+		self && by == 0
 	}
 
 	#[inline]
-	fn trailing_zeros(&self) -> u32 {
-		let mut result = 0u32;
-		for trailing in self.iter().map(BitStore::trailing_zeros) {
-			result += trailing;
-
-			if trailing != T::BITS {
-				break;
-			}
-		}
-
-		result
+	fn reverse_bits(self) -> Self {
+		self
 	}
+}
+
+impl BitStoreConst for bool {
+	const EMPTY: Self = false;
+	const FULL: Self = true;
 
 	#[inline]
-	fn trailing_ones(&self) -> u32 {
-		let mut result = 0u32;
-		for trailing in self.iter().map(BitStore::trailing_ones) {
-			result += trailing;
+	fn from_words<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+		iter.into_iter().next().is_some_and(|word| word & 1 != 0)
+	}
+}
 
-			if trailing != T::BITS {
-				break;
-			}
-		}
+impl BitStore for bool {
+	const BITS: u32 = 1;
 
-		result
-	}
+	type Word = bool;
+	const WORDS: u32 = 1;
 
 	#[inline]
-	fn leading_zeros(&self) -> u32 {
-		let mut result = 0u32;
-		for leading in self.iter().rev().map(BitStore::leading_zeros) {
-			result += leading;
-
-			if leading != T::BITS {
-				break;
-			}
+	unsafe fn get(&self, index: u32) -> bool {
+		#[cfg(debug_assertions)]
+		if index >= Self::BITS {
+			panic!(
+				"index out of bounds: the len is {} but the index is {}",
+				Self::BITS,
+				index
+			);
 		}
 
-		result
+		*self
 	}
 
 	#[inline]
-	fn leading_ones(&self) -> u32 {
-		let mut result = 0u32;
-		for leading in self.iter().rev().map(BitStore::leading_ones) {
-			result += leading;
-
-			if leading != T::BITS {
-				break;
-			}
+	unsafe fn word(&self, index: u32) -> Self::Word {
+		#[cfg(debug_assertions)]
+		if index >= Self::WORDS {
+			panic!(
+				"index out of bounds: the len is {} but the index is {}",
+				Self::WORDS,
+				index
+			);
 		}
 
-		result
+		*self
 	}
-}
 
-impl<T: BitStoreMut, const N: usize> BitStoreMut for [T; N] {
+	/// Counts the boolean directly, so `[bool; N]` stores sum their elements
+	/// as integers instead of going through a popcount per element.
 	#[inline]
-	unsafe fn set(&mut self, index: u32) {
-		let (i, j) = (index / T::BITS, index % T::BITS);
-		self[i as usize].set(j);
+	fn count_ones(&self) -> u32 {
+		*self as u32
 	}
 
 	#[inline]
-	unsafe fn unset(&mut self, index: u32) {
-		let (i, j) = (index / T::BITS, index % T::BITS);
-		self[i as usize].unset(j);
+	fn count_zeros(&self) -> u32 {
+		!*self as u32
 	}
 
 	#[inline]
-	fn union_with(&mut self, other: &Self) {
-		self
-			.iter_mut()
-			.zip(other.iter())
-			.for_each(|(x, y)| x.union_with(y));
+	unsafe fn rank(&self, index: u32) -> u32 {
+		(*self && index > 0) as u32
 	}
 
 	#[inline]
-	fn intersect_with(&mut self, other: &Self) {
-		self
-			.iter_mut()
-			.zip(other.iter())
-			.for_each(|(x, y)| x.intersect_with(y))
+	fn intersection_count(&self, other: &Self) -> u32 {
+		(*self && *other) as u32
 	}
 
 	#[inline]
-	fn difference_with(&mut self, other: &Self) {
-		self
-			.iter_mut()
-			.zip(other.iter())
-			.for_each(|(x, y)| x.difference_with(y))
+	fn next_one(&self, from: u32) -> Option<u32> {
+		(*self && from == 0).then_some(0)
 	}
 
 	#[inline]
-	fn symmetric_difference_with(&mut self, other: &Self) {
-		self
-			.iter_mut()
-			.zip(other.iter())
-			.for_each(|(x, y)| x.symmetric_difference_with(y))
+	fn trailing_zeros(&self) -> u32 {
+		!*self as u32
 	}
 
 	#[inline]
-	fn negate(&mut self) {
-		self.iter_mut().for_each(BitStoreMut::negate)
+	fn trailing_ones(&self) -> u32 {
+		*self as u32
 	}
-}
 
-macro_rules! impl_bitstore_ptr {
+	#[inline]
+	fn leading_zeros(&self) -> u32 {
+		!*self as u32
+	}
+
+	#[inline]
+	fn leading_ones(&self) -> u32 {
+		*self as u32
+	}
+
+	#[inline]
+	fn is_empty(&self) -> bool {
+		!*self
+	}
+
+	#[inline]
+	fn is_full(&self) -> bool {
+		*self
+	}
+
+	#[inline]
+	fn fold_words<B, F: FnMut(B, usize) -> B>(&self, init: B, mut f: F) -> B {
+		f(init, *self as usize)
+	}
+}
+
+impl BitStoreMut for bool {
+	#[inline]
+	unsafe fn set(&mut self, index: u32) {
+		#[cfg(debug_assertions)]
+		if index >= Self::BITS {
+			panic!(
+				"index out of bounds: the len is {} but the index is {}",
+				Self::BITS,
+				index
+			);
+		}
+
+		*self = true;
+	}
+
+	#[inline]
+	unsafe fn unset(&mut self, index: u32) {
+		#[cfg(debug_assertions)]
+		if index >= Self::BITS {
+			panic!(
+				"index out of bounds: the len is {} but the index is {}",
+				Self::BITS,
+				index
+			);
+		}
+
+		*self = false;
+	}
+
+	#[inline]
+	unsafe fn set_word(&mut self, index: u32, word: Self::Word) {
+		#[cfg(debug_assertions)]
+		if index >= Self::WORDS {
+			panic!(
+				"index out of bounds: the len is {} but the index is {}",
+				Self::WORDS,
+				index
+			);
+		}
+
+		*self = word;
+	}
+
+	#[inline]
+	fn union_with(&mut self, other: &Self) {
+		*self |= *other
+	}
+
+	#[inline]
+	fn intersect_with(&mut self, other: &Self) {
+		*self &= *other
+	}
+
+	#[inline]
+	fn difference_with(&mut self, other: &Self) {
+		*self &= !*other
+	}
+
+	#[inline]
+	fn symmetric_difference_with(&mut self, other: &Self) {
+		*self ^= *other
+	}
+
+	#[inline]
+	fn negate(&mut self) {
+		*self = !*self
+	}
+
+	#[inline]
+	fn clear(&mut self) {
+		*self = false
+	}
+
+	#[inline]
+	fn fill(&mut self) {
+		*self = true
+	}
+}
+
+impl<T: BitStoreConst, const N: usize> BitStoreConst for [T; N] {
+	const EMPTY: Self = [T::EMPTY; N];
+	const FULL: Self = [T::FULL; N];
+
+	#[inline]
+	fn from_words<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+		let mut iter = iter.into_iter();
+		core::array::from_fn(|_| T::from_words(iter.by_ref()))
+	}
+}
+
+impl<T: BitStore, const N: usize> BitStore for [T; N] {
+	const BITS: u32 = N as u32 * T::BITS;
+	type Word = T::Word;
+	const WORDS: u32 = N as u32 * T::WORDS;
+
+	#[inline]
+	unsafe fn get(&self, index: u32) -> bool {
+		let (i, j) = (index / T::BITS, index % T::BITS);
+		self[i as usize].get(j)
+	}
+
+	#[inline]
+	unsafe fn word(&self, index: u32) -> Self::Word {
+		let (i, j) = (index / T::WORDS, index % T::WORDS);
+		self[i as usize].word(j)
+	}
+
+	#[inline]
+	fn count_ones(&self) -> u32 {
+		// Summing into a single wide accumulator keeps the loop free of
+		// per-element overflow checks, which lets the compiler vectorize it
+		// (and use `popcnt` where the target supports it).
+		self.iter().map(|x| x.count_ones() as u64).sum::<u64>() as u32
+	}
+
+	#[inline]
+	fn count_zeros(&self) -> u32 {
+		self.iter().map(|x| x.count_zeros() as u64).sum::<u64>() as u32
+	}
+
+	#[inline]
+	unsafe fn rank(&self, index: u32) -> u32 {
+		let (i, j) = ((index / T::BITS) as usize, index % T::BITS);
+		let below = self[..i].iter().map(|x| x.count_ones() as u64).sum::<u64>() as u32;
+		match self.as_slice().get(i) {
+			Some(x) => below + x.rank(j),
+			None => below,
+		}
+	}
+
+	#[inline]
+	fn intersection_count(&self, other: &Self) -> u32 {
+		self
+			.iter()
+			.zip(other.iter())
+			.map(|(x, y)| x.intersection_count(y) as u64)
+			.sum::<u64>() as u32
+	}
+
+	#[inline]
+	fn next_one(&self, from: u32) -> Option<u32> {
+		let (i, j) = ((from / T::BITS) as usize, from % T::BITS);
+		let first = self
+			.as_slice()
+			.get(i)?
+			.next_one(j)
+			.map(|k| i as u32 * T::BITS + k);
+		first.or_else(|| {
+			self[i + 1..].iter().enumerate().find_map(|(n, x)| {
+				let k = x.trailing_zeros();
+				(k < T::BITS).then(|| (i + 1 + n) as u32 * T::BITS + k)
+			})
+		})
+	}
+
+	#[inline]
+	fn trailing_zeros(&self) -> u32 {
+		let mut result = 0u32;
+		for trailing in self.iter().map(BitStore::trailing_zeros) {
+			result += trailing;
+
+			if trailing != T::BITS {
+				break;
+			}
+		}
+
+		result
+	}
+
+	#[inline]
+	fn trailing_ones(&self) -> u32 {
+		let mut result = 0u32;
+		for trailing in self.iter().map(BitStore::trailing_ones) {
+			result += trailing;
+
+			if trailing != T::BITS {
+				break;
+			}
+		}
+
+		result
+	}
+
+	#[inline]
+	fn leading_zeros(&self) -> u32 {
+		let mut result = 0u32;
+		for leading in self.iter().rev().map(BitStore::leading_zeros) {
+			result += leading;
+
+			if leading != T::BITS {
+				break;
+			}
+		}
+
+		result
+	}
+
+	#[inline]
+	fn leading_ones(&self) -> u32 {
+		let mut result = 0u32;
+		for leading in self.iter().rev().map(BitStore::leading_ones) {
+			result += leading;
+
+			if leading != T::BITS {
+				break;
+			}
+		}
+
+		result
+	}
+
+	#[inline]
+	fn is_empty(&self) -> bool {
+		self.iter().all(BitStore::is_empty)
+	}
+
+	#[inline]
+	fn is_full(&self) -> bool {
+		self.iter().all(BitStore::is_full)
+	}
+}
+
+impl<T: BitStoreMut, const N: usize> BitStoreMut for [T; N] {
+	#[inline]
+	unsafe fn set(&mut self, index: u32) {
+		let (i, j) = (index / T::BITS, index % T::BITS);
+		self[i as usize].set(j);
+	}
+
+	#[inline]
+	unsafe fn unset(&mut self, index: u32) {
+		let (i, j) = (index / T::BITS, index % T::BITS);
+		self[i as usize].unset(j);
+	}
+
+	#[inline]
+	unsafe fn set_word(&mut self, index: u32, word: Self::Word) {
+		let (i, j) = (index / T::WORDS, index % T::WORDS);
+		self[i as usize].set_word(j, word);
+	}
+
+	#[inline]
+	fn union_with(&mut self, other: &Self) {
+		self
+			.iter_mut()
+			.zip(other.iter())
+			.for_each(|(x, y)| x.union_with(y));
+	}
+
+	#[inline]
+	fn intersect_with(&mut self, other: &Self) {
+		self
+			.iter_mut()
+			.zip(other.iter())
+			.for_each(|(x, y)| x.intersect_with(y))
+	}
+
+	#[inline]
+	fn difference_with(&mut self, other: &Self) {
+		self
+			.iter_mut()
+			.zip(other.iter())
+			.for_each(|(x, y)| x.difference_with(y))
+	}
+
+	#[inline]
+	fn symmetric_difference_with(&mut self, other: &Self) {
+		self
+			.iter_mut()
+			.zip(other.iter())
+			.for_each(|(x, y)| x.symmetric_difference_with(y))
+	}
+
+	#[inline]
+	fn negate(&mut self) {
+		self.iter_mut().for_each(BitStoreMut::negate)
+	}
+
+	#[inline]
+	fn clear(&mut self) {
+		self.iter_mut().for_each(BitStoreMut::clear)
+	}
+
+	#[inline]
+	fn fill(&mut self) {
+		self.iter_mut().for_each(BitStoreMut::fill)
+	}
+
+	#[inline]
+	fn is_subset_of(&self, other: &Self) -> bool {
+		self
+			.iter()
+			.zip(other.iter())
+			.all(|(x, y)| x.is_subset_of(y))
+	}
+
+	#[inline]
+	fn is_disjoint_from(&self, other: &Self) -> bool {
+		self
+			.iter()
+			.zip(other.iter())
+			.all(|(x, y)| x.is_disjoint_from(y))
+	}
+}
+
+macro_rules! impl_bitstore_ptr {
 	(const) => {
+		impl_bitstore_ptr!(@const [**] []);
+	};
+
+	(mut) => {
+		impl_bitstore_ptr!(@mut [**] []);
+	};
+
+	// Forwards to the field `$field` of a newtype rather than through a
+	// pointer.
+	(const, $field:tt) => {
+		impl_bitstore_ptr!(@const [] [.$field]);
+	};
+
+	(mut, $field:tt) => {
+		impl_bitstore_ptr!(@mut [] [.$field]);
+	};
+
+	(@const [$($pre:tt)*] [$($post:tt)*]) => {
 		#[inline]
 		unsafe fn get(&self, index: u32) -> bool {
-			BitStore::get(&**self, index)
+			BitStore::get(&$($pre)* self $($post)*, index)
+		}
+
+		#[inline]
+		unsafe fn word(&self, index: u32) -> Self::Word {
+			BitStore::word(&$($pre)* self $($post)*, index)
+		}
+
+		#[inline]
+		fn count_ones(&self) -> u32 {
+			BitStore::count_ones(&$($pre)* self $($post)*)
+		}
+
+		#[inline]
+		fn count_zeros(&self) -> u32 {
+			BitStore::count_zeros(&$($pre)* self $($post)*)
 		}
 
-		#[inline]
-		fn count_ones(&self) -> u32 {
-			BitStore::count_ones(&**self)
-		}
+		#[inline]
+		unsafe fn rank(&self, index: u32) -> u32 {
+			BitStore::rank(&$($pre)* self $($post)*, index)
+		}
+
+		#[inline]
+		fn intersection_count(&self, other: &Self) -> u32 {
+			BitStore::intersection_count(&$($pre)* self $($post)*, &$($pre)* other $($post)*)
+		}
+
+		#[inline]
+		fn next_one(&self, from: u32) -> Option<u32> {
+			BitStore::next_one(&$($pre)* self $($post)*, from)
+		}
+
+		#[inline]
+		fn trailing_zeros(&self) -> u32 {
+			BitStore::trailing_zeros(&$($pre)* self $($post)*)
+		}
+
+		#[inline]
+		fn trailing_ones(&self) -> u32 {
+			BitStore::trailing_ones(&$($pre)* self $($post)*)
+		}
+
+		#[inline]
+		fn leading_zeros(&self) -> u32 {
+			BitStore::leading_zeros(&$($pre)* self $($post)*)
+		}
+
+		#[inline]
+		fn leading_ones(&self) -> u32 {
+			BitStore::leading_ones(&$($pre)* self $($post)*)
+		}
+
+		#[inline]
+		fn is_empty(&self) -> bool {
+			BitStore::is_empty(&$($pre)* self $($post)*)
+		}
+
+		#[inline]
+		fn is_full(&self) -> bool {
+			BitStore::is_full(&$($pre)* self $($post)*)
+		}
+	};
+
+	(@mut [$($pre:tt)*] [$($post:tt)*]) => {
+		#[inline]
+		unsafe fn set(&mut self, index: u32) {
+			BitStoreMut::set(&mut $($pre)* self $($post)*, index)
+		}
+
+		#[inline]
+		unsafe fn unset(&mut self, index: u32) {
+			BitStoreMut::unset(&mut $($pre)* self $($post)*, index)
+		}
+
+		#[inline]
+		unsafe fn set_word(&mut self, index: u32, word: Self::Word) {
+			BitStoreMut::set_word(&mut $($pre)* self $($post)*, index, word)
+		}
+
+		#[inline]
+		fn union_with(&mut self, other: &Self) {
+			BitStoreMut::union_with(&mut $($pre)* self $($post)*, &$($pre)* other $($post)*)
+		}
+
+		#[inline]
+		fn intersect_with(&mut self, other: &Self) {
+			BitStoreMut::intersect_with(&mut $($pre)* self $($post)*, &$($pre)* other $($post)*)
+		}
+
+		#[inline]
+		fn difference_with(&mut self, other: &Self) {
+			BitStoreMut::difference_with(&mut $($pre)* self $($post)*, &$($pre)* other $($post)*)
+		}
+
+		#[inline]
+		fn symmetric_difference_with(&mut self, other: &Self) {
+			BitStoreMut::symmetric_difference_with(&mut $($pre)* self $($post)*, &$($pre)* other $($post)*)
+		}
+
+		#[inline]
+		fn negate(&mut self) {
+			BitStoreMut::negate(&mut $($pre)* self $($post)*)
+		}
+
+		#[inline]
+		fn clear(&mut self) {
+			BitStoreMut::clear(&mut $($pre)* self $($post)*)
+		}
+
+		#[inline]
+		fn fill(&mut self) {
+			BitStoreMut::fill(&mut $($pre)* self $($post)*)
+		}
+
+		#[inline]
+		unsafe fn set_range(&mut self, range: ops::Range<u32>) {
+			BitStoreMut::set_range(&mut $($pre)* self $($post)*, range)
+		}
+
+		#[inline]
+		unsafe fn unset_range(&mut self, range: ops::Range<u32>) {
+			BitStoreMut::unset_range(&mut $($pre)* self $($post)*, range)
+		}
+
+		#[inline]
+		unsafe fn toggle_range(&mut self, range: ops::Range<u32>) {
+			BitStoreMut::toggle_range(&mut $($pre)* self $($post)*, range)
+		}
+
+		#[inline]
+		fn is_subset_of(&self, other: &Self) -> bool {
+			BitStoreMut::is_subset_of(&$($pre)* self $($post)*, &$($pre)* other $($post)*)
+		}
+
+		#[inline]
+		fn is_disjoint_from(&self, other: &Self) -> bool {
+			BitStoreMut::is_disjoint_from(&$($pre)* self $($post)*, &$($pre)* other $($post)*)
+		}
+	};
+}
+
+impl<T: BitStore> BitStore for &T {
+	type Word = T::Word;
+	const BITS: u32 = <T as BitStore>::BITS;
+	const WORDS: u32 = <T as BitStore>::WORDS;
+	impl_bitstore_ptr!(const);
+}
+
+impl<T: BitStore> BitStore for &mut T {
+	type Word = T::Word;
+	const BITS: u32 = <T as BitStore>::BITS;
+	const WORDS: u32 = <T as BitStore>::WORDS;
+	impl_bitstore_ptr!(const);
+}
+
+impl<T: BitStoreMut> BitStoreMut for &mut T {
+	impl_bitstore_ptr!(mut);
+}
+
+#[cfg(feature = "alloc")]
+impl<T: BitStore> BitStore for alloc::boxed::Box<T> {
+	type Word = T::Word;
+	const BITS: u32 = <T as BitStore>::BITS;
+	const WORDS: u32 = <T as BitStore>::WORDS;
+	impl_bitstore_ptr!(const);
+}
+
+#[cfg(feature = "alloc")]
+impl<T: BitStoreMut> BitStoreMut for alloc::boxed::Box<T> {
+	impl_bitstore_ptr!(mut);
+}
+
+/// A store of `N` 64-bit words that is created by zeroing memory rather than
+/// by writing each word individually.
+///
+/// This is intended for large buffers that are reused, where the backing
+/// memory can be zeroed once (for instance through [ZeroedStore::zero_in_place])
+/// and then used as a bit store without any further initialization.
+///
+/// # Safety invariant
+/// All `N` words of a `ZeroedStore` are always initialized. Every constructor
+/// upholds this by zeroing the full memory of the store, which is sound because
+/// the all-zero bit pattern is a valid `u64`.
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZeroedStore<const N: usize>([u64; N]);
+
+impl<const N: usize> ZeroedStore<N> {
+	/// Creates a new store with all bits set to 0.
+	#[inline]
+	pub const fn zeroed() -> Self {
+		// SAFETY: the all-zero bit pattern is a valid `[u64; N]`.
+		Self(unsafe { MaybeUninit::<[u64; N]>::zeroed().assume_init() })
+	}
+
+	/// Zeroes a possibly uninitialized slot in place, and returns a reference
+	/// to the now initialized store.
+	#[inline]
+	pub fn zero_in_place(slot: &mut MaybeUninit<Self>) -> &mut Self {
+		// SAFETY: `slot` is valid for writes of one `Self`, and after zeroing
+		// every word is initialized.
+		unsafe {
+			slot.as_mut_ptr().write_bytes(0, 1);
+			slot.assume_init_mut()
+		}
+	}
+
+	/// Sets all bits of an existing store to 0, reusing its memory.
+	#[inline]
+	pub fn reset(&mut self) {
+		self.0 = [0; N];
+	}
+
+	/// Returns a reference to the words backing this store.
+	#[inline]
+	pub const fn as_words(&self) -> &[u64; N] {
+		&self.0
+	}
+
+	/// Returns the words backing this store.
+	#[inline]
+	pub const fn into_words(self) -> [u64; N] {
+		self.0
+	}
+}
+
+impl<const N: usize> Default for ZeroedStore<N> {
+	#[inline]
+	fn default() -> Self {
+		Self::zeroed()
+	}
+}
+
+// SAFETY: `Default` is implemented through `ZeroedStore::zeroed`.
+unsafe impl<const N: usize> DefaultIsEmpty for ZeroedStore<N> {}
+
+impl<const N: usize> BitStoreConst for ZeroedStore<N> {
+	const EMPTY: Self = Self::zeroed();
+	const FULL: Self = Self(<[u64; N]>::FULL);
+
+	#[inline]
+	fn from_words<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+		Self(<[u64; N]>::from_words(iter))
+	}
+}
+
+impl<const N: usize> BitStore for ZeroedStore<N> {
+	type Word = u64;
+	const BITS: u32 = <[u64; N] as BitStore>::BITS;
+	const WORDS: u32 = N as u32;
+	impl_bitstore_ptr!(const, 0);
+}
+
+impl<const N: usize> BitStoreMut for ZeroedStore<N> {
+	impl_bitstore_ptr!(mut, 0);
+}
+
+/// A store that numbers its bits from the most significant bit of the
+/// wrapped store, i.e., index 0 is the highest bit of `S`.
+///
+/// Operations that don't depend on the order of the bits, such as
+/// [BitStore::count_ones] and the set operations, are forwarded to `S`
+/// unchanged, while leading and trailing counts trade places.
+#[repr(transparent)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MsbFirst<S>(pub S);
+
+// SAFETY: `MsbFirst` has the same bits as the wrapped store.
+unsafe impl<S: DefaultIsEmpty> DefaultIsEmpty for MsbFirst<S> {}
+
+impl<S: BitStoreConst> BitStoreConst for MsbFirst<S> {
+	const EMPTY: Self = Self(S::EMPTY);
+	const FULL: Self = Self(S::FULL);
+
+	/// The words fill the wrapped store as-is, without reordering any bits.
+	#[inline]
+	fn from_words<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+		Self(S::from_words(iter))
+	}
+}
+
+impl<S: BitStore> BitStore for MsbFirst<S> {
+	type Word = S::Word;
+	const BITS: u32 = S::BITS;
+	const WORDS: u32 = S::WORDS;
+
+	#[inline]
+	unsafe fn get(&self, index: u32) -> bool {
+		self.0.get(S::BITS - 1 - index)
+	}
+
+	#[inline]
+	unsafe fn word(&self, index: u32) -> Self::Word {
+		self.0.word(S::WORDS - 1 - index).reverse_bits()
+	}
+
+	#[inline]
+	fn count_ones(&self) -> u32 {
+		self.0.count_ones()
+	}
+
+	#[inline]
+	fn count_zeros(&self) -> u32 {
+		self.0.count_zeros()
+	}
+
+	#[inline]
+	unsafe fn rank(&self, index: u32) -> u32 {
+		self.0.count_ones() - self.0.rank(S::BITS - index)
+	}
+
+	#[inline]
+	fn intersection_count(&self, other: &Self) -> u32 {
+		self.0.intersection_count(&other.0)
+	}
+
+	#[inline]
+	fn trailing_zeros(&self) -> u32 {
+		self.0.leading_zeros()
+	}
+
+	#[inline]
+	fn trailing_ones(&self) -> u32 {
+		self.0.leading_ones()
+	}
+
+	#[inline]
+	fn leading_zeros(&self) -> u32 {
+		self.0.trailing_zeros()
+	}
+
+	#[inline]
+	fn leading_ones(&self) -> u32 {
+		self.0.trailing_ones()
+	}
+
+	#[inline]
+	fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	#[inline]
+	fn is_full(&self) -> bool {
+		self.0.is_full()
+	}
+}
+
+impl<S: BitStoreMut> BitStoreMut for MsbFirst<S> {
+	#[inline]
+	unsafe fn set(&mut self, index: u32) {
+		self.0.set(S::BITS - 1 - index)
+	}
+
+	#[inline]
+	unsafe fn unset(&mut self, index: u32) {
+		self.0.unset(S::BITS - 1 - index)
+	}
+
+	#[inline]
+	unsafe fn set_word(&mut self, index: u32, word: Self::Word) {
+		self.0.set_word(S::WORDS - 1 - index, word.reverse_bits())
+	}
+
+	#[inline]
+	fn union_with(&mut self, other: &Self) {
+		self.0.union_with(&other.0)
+	}
+
+	#[inline]
+	fn intersect_with(&mut self, other: &Self) {
+		self.0.intersect_with(&other.0)
+	}
+
+	#[inline]
+	fn difference_with(&mut self, other: &Self) {
+		self.0.difference_with(&other.0)
+	}
+
+	#[inline]
+	fn symmetric_difference_with(&mut self, other: &Self) {
+		self.0.symmetric_difference_with(&other.0)
+	}
+
+	#[inline]
+	fn negate(&mut self) {
+		self.0.negate()
+	}
+
+	#[inline]
+	fn clear(&mut self) {
+		self.0.clear()
+	}
 
-		#[inline]
-		fn trailing_zeros(&self) -> u32 {
-			BitStore::trailing_zeros(&**self)
-		}
+	#[inline]
+	fn fill(&mut self) {
+		self.0.fill()
+	}
 
-		#[inline]
-		fn trailing_ones(&self) -> u32 {
-			BitStore::trailing_ones(&**self)
-		}
+	#[inline]
+	fn is_subset_of(&self, other: &Self) -> bool {
+		self.0.is_subset_of(&other.0)
+	}
 
-		#[inline]
-		fn leading_zeros(&self) -> u32 {
-			BitStore::leading_zeros(&**self)
-		}
+	#[inline]
+	fn is_disjoint_from(&self, other: &Self) -> bool {
+		self.0.is_disjoint_from(&other.0)
+	}
+}
 
-		#[inline]
-		fn leading_ones(&self) -> u32 {
-			BitStore::leading_ones(&**self)
-		}
-	};
+macro_rules! impl_bitstore_cell {
+	($ty:ty) => {
+		// SAFETY: `Cell::default` contains the default value of the integer, which is 0.
+		unsafe impl DefaultIsEmpty for Cell<$ty> {}
 
-	(mut) => {
-		#[inline]
-		unsafe fn set(&mut self, index: u32) {
-			BitStoreMut::set(&mut **self, index)
-		}
+		impl BitStore for Cell<$ty> {
+			type Word = $ty;
+			const BITS: u32 = <$ty as BitStore>::BITS;
+			const WORDS: u32 = 1;
 
-		#[inline]
-		unsafe fn unset(&mut self, index: u32) {
-			BitStoreMut::unset(&mut **self, index)
-		}
+			#[inline]
+			unsafe fn get(&self, index: u32) -> bool {
+				BitStore::get(&Cell::get(self), index)
+			}
 
-		#[inline]
-		fn union_with(&mut self, other: &Self) {
-			BitStoreMut::union_with(&mut **self, other)
-		}
+			#[inline]
+			unsafe fn word(&self, index: u32) -> Self::Word {
+				BitStore::word(&Cell::get(self), index)
+			}
 
-		#[inline]
-		fn intersect_with(&mut self, other: &Self) {
-			BitStoreMut::intersect_with(&mut **self, other)
-		}
+			#[inline]
+			fn count_ones(&self) -> u32 {
+				<$ty>::count_ones(Cell::get(self))
+			}
 
-		#[inline]
-		fn difference_with(&mut self, other: &Self) {
-			BitStoreMut::difference_with(&mut **self, other)
-		}
+			#[inline]
+			fn count_zeros(&self) -> u32 {
+				<$ty>::count_zeros(Cell::get(self))
+			}
 
-		#[inline]
-		fn symmetric_difference_with(&mut self, other: &Self) {
-			BitStoreMut::symmetric_difference_with(&mut **self, other)
+			#[inline]
+			unsafe fn rank(&self, index: u32) -> u32 {
+				BitStore::rank(&Cell::get(self), index)
+			}
+
+			#[inline]
+			fn intersection_count(&self, other: &Self) -> u32 {
+				<$ty>::count_ones(Cell::get(self) & Cell::get(other))
+			}
+
+			#[inline]
+			fn next_one(&self, from: u32) -> Option<u32> {
+				BitStore::next_one(&Cell::get(self), from)
+			}
+
+			#[inline]
+			fn trailing_zeros(&self) -> u32 {
+				<$ty>::trailing_zeros(Cell::get(self))
+			}
+
+			#[inline]
+			fn trailing_ones(&self) -> u32 {
+				<$ty>::trailing_ones(Cell::get(self))
+			}
+
+			#[inline]
+			fn leading_zeros(&self) -> u32 {
+				<$ty>::leading_zeros(Cell::get(self))
+			}
+
+			#[inline]
+			fn leading_ones(&self) -> u32 {
+				<$ty>::leading_ones(Cell::get(self))
+			}
+
+			#[inline]
+			fn is_empty(&self) -> bool {
+				Cell::get(self) == 0
+			}
+
+			#[inline]
+			fn is_full(&self) -> bool {
+				Cell::get(self) == !0
+			}
 		}
 
-		#[inline]
-		fn negate(&mut self) {
-			BitStoreMut::negate(&mut **self)
+		impl BitStoreShared for Cell<$ty> {
+			#[inline]
+			unsafe fn set_shared(&self, index: u32) {
+				let mut value = Cell::get(self);
+				value.set(index);
+				Cell::set(self, value);
+			}
+
+			#[inline]
+			unsafe fn unset_shared(&self, index: u32) {
+				let mut value = Cell::get(self);
+				value.unset(index);
+				Cell::set(self, value);
+			}
 		}
 	};
 }
 
-impl<'a, T: BitStore> BitStore for &'a T {
-	const BITS: u32 = <T as BitStore>::BITS;
-	impl_bitstore_ptr!(const);
-}
+impl_bitstore_cell!(u8);
+impl_bitstore_cell!(u16);
+impl_bitstore_cell!(u32);
+impl_bitstore_cell!(u64);
+impl_bitstore_cell!(u128);
+impl_bitstore_cell!(usize);
 
-impl<'a, T: BitStore> BitStore for &'a mut T {
-	const BITS: u32 = <T as BitStore>::BITS;
-	impl_bitstore_ptr!(const);
-}
+impl<T: BitStoreShared, const N: usize> BitStoreShared for [T; N] {
+	#[inline]
+	unsafe fn set_shared(&self, index: u32) {
+		let (i, j) = (index / T::BITS, index % T::BITS);
+		self[i as usize].set_shared(j);
+	}
 
-impl<'a, T: BitStoreMut> BitStoreMut for &'a mut T {
-	impl_bitstore_ptr!(mut);
+	#[inline]
+	unsafe fn unset_shared(&self, index: u32) {
+		let (i, j) = (index / T::BITS, index % T::BITS);
+		self[i as usize].unset_shared(j);
+	}
 }
 
-#[cfg(feature = "alloc")]
-impl<T: BitStore> BitStore for alloc::boxed::Box<T> {
-	const BITS: u32 = <T as BitStore>::BITS;
-	impl_bitstore_ptr!(const);
-}
+impl<T: BitStoreShared> BitStoreShared for &T {
+	#[inline]
+	unsafe fn set_shared(&self, index: u32) {
+		BitStoreShared::set_shared(&**self, index)
+	}
 
-#[cfg(feature = "alloc")]
-impl<T: BitStoreMut> BitStoreMut for alloc::boxed::Box<T> {
-	impl_bitstore_ptr!(mut);
+	#[inline]
+	unsafe fn unset_shared(&self, index: u32) {
+		BitStoreShared::unset_shared(&**self, index)
+	}
 }
 
 #[cfg(test)]
@@ -463,6 +1634,120 @@ mod tests {
 				);
 			}
 
+			#[test]
+			fn words_cover_bits() {
+				assert_eq!(
+					<$ty as BitStore>::WORDS * <<$ty as BitStore>::Word as BitStore>::BITS,
+					<$ty as BitStore>::BITS
+				);
+			}
+
+			#[test]
+			fn any_individual_index_maps_to_word() {
+				let word_bits = <<$ty as BitStore>::Word as BitStore>::BITS;
+				for i in 0..<$ty as BitStore>::BITS {
+					let mut x = <$ty as BitStoreConst>::EMPTY;
+					unsafe { x.set(i) };
+
+					for w in 0..<$ty as BitStore>::WORDS {
+						let word = unsafe { BitStore::word(&x, w) };
+						if w == i / word_bits {
+							assert_eq!(BitStore::count_ones(&word), 1, "count_ones()");
+							assert_eq!(unsafe { BitStore::get(&word, i % word_bits) }, true);
+						} else {
+							assert!(BitStore::is_empty(&word));
+						}
+					}
+
+					let mut y = <$ty as BitStoreConst>::EMPTY;
+					unsafe { y.set_word(i / word_bits, BitStore::word(&x, i / word_bits)) };
+					assert_eq!(unsafe { BitStore::get(&y, i) }, true);
+					assert_eq!(BitStore::count_ones(&y), 1, "count_ones()");
+				}
+			}
+
+			#[test]
+			fn count_zeros_complements_count_ones() {
+				assert_eq!(
+					BitStore::count_zeros(&<$ty as BitStoreConst>::EMPTY),
+					<$ty as BitStore>::BITS
+				);
+				assert_eq!(BitStore::count_zeros(&<$ty as BitStoreConst>::FULL), 0);
+
+				let mut x = <$ty as BitStoreConst>::EMPTY;
+				for i in (0..<$ty as BitStore>::BITS).step_by(3) {
+					unsafe { x.set(i) };
+				}
+				assert_eq!(
+					BitStore::count_zeros(&x) + BitStore::count_ones(&x),
+					<$ty as BitStore>::BITS
+				);
+			}
+
+			#[test]
+			fn rank_counts_bits_below() {
+				let mut x = <$ty as BitStoreConst>::EMPTY;
+				for i in (0..<$ty as BitStore>::BITS).step_by(3) {
+					unsafe { x.set(i) };
+				}
+
+				let mut expected = 0;
+				for i in 0..<$ty as BitStore>::BITS {
+					assert_eq!(unsafe { BitStore::rank(&x, i) }, expected, "rank({})", i);
+					expected += unsafe { BitStore::bit(&x, i) };
+				}
+				assert_eq!(
+					unsafe { BitStore::rank(&x, <$ty as BitStore>::BITS) },
+					BitStore::count_ones(&x)
+				);
+			}
+
+			#[test]
+			fn intersection_count_matches_intersect_with() {
+				let mut x = <$ty as BitStoreConst>::EMPTY;
+				let mut y = <$ty as BitStoreConst>::EMPTY;
+				for i in 0..<$ty as BitStore>::BITS {
+					if i % 3 == 0 {
+						unsafe { x.set(i) };
+					}
+					if i % 2 == 0 {
+						unsafe { y.set(i) };
+					}
+				}
+
+				let count = x.intersection_count(&y);
+				x.intersect_with(&y);
+				assert_eq!(count, BitStore::count_ones(&x));
+			}
+
+			#[test]
+			fn next_one_finds_following_set_bit() {
+				let mut x = <$ty as BitStoreConst>::EMPTY;
+				for i in (0..<$ty as BitStore>::BITS).step_by(3) {
+					unsafe { x.set(i) };
+				}
+
+				for from in 0..<$ty as BitStore>::BITS {
+					let expected = (from..<$ty as BitStore>::BITS).find(|&i| i % 3 == 0);
+					assert_eq!(BitStore::next_one(&x, from), expected, "next_one({})", from);
+				}
+				assert_eq!(BitStore::next_one(&x, <$ty as BitStore>::BITS), None);
+				assert_eq!(BitStore::next_one(&<$ty as BitStoreConst>::EMPTY, 0), None);
+			}
+
+			#[test]
+			fn bit_matches_get() {
+				let mut x = <$ty as BitStoreConst>::EMPTY;
+				for i in (0..<$ty as BitStore>::BITS).step_by(3) {
+					unsafe { x.set(i) };
+				}
+
+				for i in 0..<$ty as BitStore>::BITS {
+					let expected = if i % 3 == 0 { 1 } else { 0 };
+					assert_eq!(unsafe { BitStore::bit(&x, i) }, expected);
+				}
+			}
+
 			#[test]
 			fn zero_is_all_false() {
 				let x = <$ty as BitStoreConst>::EMPTY;
@@ -495,6 +1780,70 @@ mod tests {
 				}
 			}
 
+			#[test]
+			fn any_individual_index_breaks_empty_and_full() {
+				for i in 0..<$ty as BitStore>::BITS {
+					let mut x = <$ty as BitStoreConst>::EMPTY;
+					unsafe { x.set(i) };
+					assert!(!BitStore::is_empty(&x), "is_empty()");
+
+					let mut x = <$ty as BitStoreConst>::FULL;
+					unsafe { x.unset(i) };
+					assert!(!BitStore::is_full(&x), "is_full()");
+				}
+			}
+
+			#[test]
+			fn clear_and_fill() {
+				let mut x = <$ty as BitStoreConst>::FULL;
+				x.clear();
+				assert!(BitStore::is_empty(&x));
+				x.fill();
+				assert!(BitStore::is_full(&x));
+			}
+
+			#[test]
+			fn set_range_matches_individual_sets() {
+				let bits = <$ty as BitStore>::BITS;
+				for range in [0..0, 0..1, 1..bits / 2, bits / 3..bits - 1, 0..bits] {
+					let mut x = <$ty as BitStoreConst>::EMPTY;
+					unsafe { x.set_range(range.clone()) };
+					for i in 0..bits {
+						assert_eq!(unsafe { BitStore::get(&x, i) }, range.contains(&i));
+					}
+
+					unsafe { x.unset_range(range.clone()) };
+					assert!(BitStore::is_empty(&x));
+
+					unsafe { x.toggle_range(range.clone()) };
+					assert_eq!(BitStore::count_ones(&x), range.len() as u32);
+					unsafe { x.toggle_range(range.clone()) };
+					assert!(BitStore::is_empty(&x));
+				}
+			}
+
+			#[test]
+			fn subset_and_disjoint_match_set_ops() {
+				let mut x = <$ty as BitStoreConst>::EMPTY;
+				let mut y = <$ty as BitStoreConst>::EMPTY;
+				for i in (0..<$ty as BitStore>::BITS).step_by(3) {
+					unsafe { x.set(i) };
+					unsafe { y.set(i) };
+				}
+
+				assert!(x.is_subset_of(&y));
+				assert!(!x.is_disjoint_from(&y));
+
+				unsafe { y.unset(0) };
+				assert!(!x.is_subset_of(&y));
+				assert!(y.is_subset_of(&x));
+
+				y.negate();
+				assert!(!x.is_disjoint_from(&y));
+				assert!(<$ty as BitStoreConst>::EMPTY.is_disjoint_from(&y));
+				assert!(<$ty as BitStoreConst>::EMPTY.is_subset_of(&x));
+			}
+
 			#[test]
 			fn negate() {
 				let mut x = <$ty as BitStoreConst>::EMPTY;
@@ -619,4 +1968,203 @@ mod tests {
 	test_bitstore!(u64, u64_bitstore);
 	test_bitstore!(u128, u128_bitstore);
 	test_bitstore!(usize, usize_bitstore);
+
+	#[test]
+	fn fold_words_sums_words() {
+		let words = [1u32, 20, 300, 4000];
+		assert_eq!(words.fold_words(0, |acc, w| acc + w), 4321);
+		assert_eq!(0xffu8.fold_words(0, |acc, w| acc + w), 0xff);
+
+		let mut chunks = alloc::vec::Vec::new();
+		(u128::MAX - 1).fold_words((), |(), w| chunks.push(w));
+		assert_eq!(chunks.len() as u32, 128 / usize::BITS);
+		assert_eq!(chunks[0], usize::MAX - 1);
+	}
+
+	#[test]
+	fn from_words_pads_and_truncates() {
+		assert_eq!(<[u16; 3]>::from_words([]), [0, 0, 0]);
+		assert_eq!(<[u16; 3]>::from_words([1, 2]), [1, 2, 0]);
+		assert_eq!(<[u16; 3]>::from_words([1, 2, 3]), [1, 2, 3]);
+		assert_eq!(<[u16; 3]>::from_words([1, 2, 3, 4]), [1, 2, 3]);
+		assert_eq!(<[u16; 3]>::from_words([0x1_ffff, 2, 3]), [0xffff, 2, 3]);
+		assert_eq!(<[[u8; 2]; 2]>::from_words([1, 2, 3]), [[1, 2], [3, 0]]);
+	}
+
+	#[test]
+	fn from_words_round_trips_wide_words() {
+		fn words<S: BitStore>(store: &S) -> alloc::vec::Vec<usize> {
+			store.fold_words(alloc::vec::Vec::new(), |mut acc, w| {
+				acc.push(w);
+				acc
+			})
+		}
+
+		let x = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128;
+		assert_eq!(u128::from_words(words(&x)), x);
+
+		let xs = [x, !x];
+		assert_eq!(words(&xs).len() as u32, 256 / usize::BITS);
+		assert_eq!(<[u128; 2]>::from_words(words(&xs)), xs);
+		assert_eq!(<[u128; 2]>::from_words([1]), [1, 0]);
+	}
+
+	#[test]
+	fn count_ones_large_array() {
+		let mut x = <[u64; 300] as BitStoreConst>::EMPTY;
+		let mut expected = 0;
+		for i in (0..<[u64; 300] as BitStore>::BITS).filter(|i| i % 7 == 0 || i % 11 == 0) {
+			unsafe { x.set(i) };
+			expected += 1;
+		}
+
+		assert_eq!(BitStore::count_ones(&x), expected);
+		assert_eq!(
+			BitStore::count_ones(&<[u64; 300] as BitStoreConst>::FULL),
+			300 * 64
+		);
+	}
+
+	mod nested_bitstore {
+		use super::*;
+
+		test_bitstore!(@tests [[u8; 2]; 3]);
+
+		#[test]
+		fn index_routing() {
+			let mut x = <[[u8; 2]; 3] as BitStoreConst>::EMPTY;
+			unsafe { x.set(0) };
+			unsafe { x.set(9) };
+			unsafe { x.set(23) };
+			unsafe { x.set(47) };
+			assert_eq!(
+				x,
+				[
+					[0b0000_0001, 0b0000_0010],
+					[0b1000_0000, 0],
+					[0, 0b1000_0000]
+				]
+			);
+
+			unsafe { x.unset(23) };
+			assert_eq!(x, [[0b0000_0001, 0b0000_0010], [0, 0], [0, 0b1000_0000]]);
+		}
+
+		#[test]
+		fn zeros_across_boundaries() {
+			let x = <[[u8; 2]; 3] as BitStoreConst>::EMPTY;
+			assert_eq!(BitStore::trailing_zeros(&x), 48);
+			assert_eq!(BitStore::leading_zeros(&x), 48);
+
+			let x = [[0u8, 0], [0, 0b0001_0000], [0, 0]];
+			assert_eq!(BitStore::trailing_zeros(&x), 28);
+			assert_eq!(BitStore::leading_zeros(&x), 19);
+		}
+
+		#[test]
+		fn ones_across_boundaries() {
+			let x = <[[u8; 2]; 3] as BitStoreConst>::FULL;
+			assert_eq!(BitStore::trailing_ones(&x), 48);
+			assert_eq!(BitStore::leading_ones(&x), 48);
+
+			let x = [[!0u8, !0], [!0, 0b1110_1111], [!0, !0]];
+			assert_eq!(BitStore::trailing_ones(&x), 28);
+			assert_eq!(BitStore::leading_ones(&x), 19);
+		}
+	}
+
+	mod zeroed_bitstore {
+		use super::*;
+
+		test_bitstore!(@tests ZeroedStore<4>);
+
+		#[test]
+		fn zeroed_is_empty() {
+			assert!(BitStore::is_empty(&ZeroedStore::<16>::zeroed()));
+			assert!(BitStore::is_empty(&ZeroedStore::<16>::default()));
+		}
+
+		#[test]
+		fn zero_in_place() {
+			let mut slot = MaybeUninit::<ZeroedStore<16>>::uninit();
+			let x = ZeroedStore::zero_in_place(&mut slot);
+			assert!(BitStore::is_empty(x));
+
+			unsafe { x.set(700) };
+			assert!(unsafe { BitStore::get(x, 700) });
+			assert_eq!(BitStore::count_ones(x), 1);
+		}
+
+		#[test]
+		fn reset() {
+			let mut x = <ZeroedStore<16> as BitStoreConst>::FULL;
+			x.reset();
+			assert!(BitStore::is_empty(&x));
+
+			unsafe { x.set(65) };
+			assert_eq!(x.as_words()[1], 0b10);
+			assert_eq!(x.into_words()[1], 0b10);
+		}
+	}
+
+	mod msb_first_bitstore {
+		use super::*;
+
+		test_bitstore!(@tests MsbFirst<u8>);
+
+		mod x3 {
+			use super::*;
+			test_bitstore!(@tests MsbFirst<[u16; 3]>);
+		}
+
+		#[test]
+		fn index_zero_is_msb() {
+			let mut x = MsbFirst(0u8);
+			unsafe { x.set(0) };
+			assert_eq!(x.0, 0b1000_0000);
+
+			unsafe { x.set(7) };
+			assert_eq!(x.0, 0b1000_0001);
+
+			let x = MsbFirst([0u8, 0b0100_0000]);
+			assert!(unsafe { BitStore::get(&x, 1) });
+			assert_eq!(BitStore::trailing_zeros(&x), 1);
+			assert_eq!(BitStore::leading_zeros(&x), 14);
+			assert_eq!(unsafe { BitStore::word(&x, 0) }, 0b0000_0010);
+		}
+	}
+
+	mod cell_bitstore {
+		use super::*;
+
+		#[test]
+		fn set_shared() {
+			let x = Cell::new(0u16);
+			let y = &x;
+			unsafe { y.set_shared(3) };
+			unsafe { y.set_shared(12) };
+			assert_eq!(x.get(), 0b0001_0000_0000_1000);
+			assert_eq!(BitStore::count_ones(&x), 2);
+			assert_eq!(BitStore::trailing_zeros(&x), 3);
+			assert_eq!(BitStore::leading_zeros(&x), 3);
+
+			unsafe { y.unset_shared(3) };
+			assert_eq!(x.get(), 0b0001_0000_0000_0000);
+			assert!(unsafe { BitStore::get(&x, 12) });
+		}
+
+		#[test]
+		fn array_set_shared() {
+			let x: [Cell<u8>; 3] = Default::default();
+			unsafe { x.set_shared(0) };
+			unsafe { x.set_shared(9) };
+			unsafe { x.set_shared(23) };
+			assert_eq!(x.clone().map(Cell::into_inner), [0b1, 0b10, 0b1000_0000]);
+			assert_eq!(BitStore::count_ones(&x), 3);
+			assert_eq!(unsafe { BitStore::word(&x, 1) }, 0b10);
+
+			unsafe { x.unset_shared(9) };
+			assert_eq!(x.map(Cell::into_inner), [0b1, 0, 0b1000_0000]);
+		}
+	}
 }